@@ -0,0 +1,140 @@
+use crate::{Params, Part, Solution};
+use anyhow::Result;
+use std::{
+    fmt::{self, Display},
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// A separately timed step of solving a day.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Part(Part),
+}
+
+impl Phase {
+    /// Parsing followed by each of `parts`.
+    pub fn all(parts: &[Part]) -> Vec<Phase> {
+        let parts = parts.iter().map(|&part| Phase::Part(part));
+        [Phase::Parse].into_iter().chain(parts).collect()
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part(part) => write!(f, "part {part}"),
+        }
+    }
+}
+
+/// How often to run a phase: `warmup` untimed runs, then `samples` timed ones.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Runs {
+    pub warmup: usize,
+    pub samples: usize,
+}
+
+impl Default for Runs {
+    fn default() -> Self {
+        Runs {
+            warmup: 3,
+            samples: 10,
+        }
+    }
+}
+
+/// Times `phase` of `S` on `input`. The parts are timed on an input parsed once up front.
+pub fn sample<S: Solution>(
+    input: &str,
+    phase: Phase,
+    runs: Runs,
+    params: &Params,
+) -> Result<Vec<Duration>> {
+    match phase {
+        Phase::Parse => repeat(runs, || S::parse(input)),
+        Phase::Part(part) => {
+            let input = S::parse(input)?;
+            match part {
+                Part::One => repeat(runs, || S::part1_with(&input, params)),
+                Part::Two => repeat(runs, || S::part2_with(&input, params)),
+            }
+        }
+    }
+}
+
+fn repeat<T>(runs: Runs, mut f: impl FnMut() -> Result<T>) -> Result<Vec<Duration>> {
+    for _ in 0..runs.warmup {
+        black_box(f()?);
+    }
+    (0..runs.samples)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f()?);
+            Ok(start.elapsed())
+        })
+        .collect()
+}
+
+/// Registers a Criterion benchmark per phase of `S`, on the cached input if there is one and
+/// on the example, with the example parameters, otherwise. Phases that fail on that input are
+/// skipped.
+#[cfg(feature = "criterion")]
+pub fn criterion<S: Solution>(c: &mut criterion::Criterion) {
+    use crate::input::input_path;
+    use std::{
+        env, fs,
+        panic::{self, AssertUnwindSafe},
+        path::PathBuf,
+    };
+
+    let dir = env::var_os("AOC_INPUTS").map_or_else(
+        || PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../inputs"),
+        PathBuf::from,
+    );
+    let found = [false, true]
+        .map(|example| (example, input_path(&dir, S::DAY, example)))
+        .into_iter()
+        .find(|(_, path)| path.is_file());
+    let Some((example, text)) = found.and_then(|(example, path)| {
+        let text = fs::read_to_string(path).ok()?;
+        Some((example, text))
+    }) else {
+        eprintln!("day {}: no input in {}", S::DAY, dir.display());
+        return;
+    };
+
+    let input = match S::parse(&text) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("day {}: {err:#}", S::DAY);
+            return;
+        }
+    };
+    let mut group = c.benchmark_group(format!("day{:02}", S::DAY));
+    group.sample_size(10);
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&text))));
+    let params = Params::new(S::PARAMS, example);
+    for &part in S::PARTS {
+        let name = format!("part{part}");
+        let works = panic::catch_unwind(AssertUnwindSafe(|| match part {
+            Part::One => S::part1_with(&input, &params).is_ok(),
+            Part::Two => S::part2_with(&input, &params).is_ok(),
+        }));
+        if !matches!(works, Ok(true)) {
+            eprintln!("day {}: skipping {name}, which fails on this input", S::DAY);
+            continue;
+        }
+        match part {
+            Part::One => group.bench_function(name, |b| {
+                b.iter(|| S::part1_with(black_box(&input), &params))
+            }),
+            Part::Two => group.bench_function(name, |b| {
+                b.iter(|| S::part2_with(black_box(&input), &params))
+            }),
+        };
+    }
+    group.finish();
+}
@@ -0,0 +1,281 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Rem, Sub, SubAssign};
+
+/// A point or a displacement on the plane, as a row and a column.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Pos {
+    pub r: i32,
+    pub c: i32,
+}
+
+impl Pos {
+    pub const ORIGIN: Pos = Pos { r: 0, c: 0 };
+
+    pub const fn new(r: i32, c: i32) -> Self {
+        Pos { r, c }
+    }
+
+    pub fn manhattan(self, other: Pos) -> u32 {
+        self.r.abs_diff(other.r) + self.c.abs_diff(other.c)
+    }
+
+    pub fn chebyshev(self, other: Pos) -> u32 {
+        self.r.abs_diff(other.r).max(self.c.abs_diff(other.c))
+    }
+
+    /// Wraps the position around a torus of the given size.
+    pub fn wrap(self, size: Pos) -> Pos {
+        Pos {
+            r: self.r.rem_euclid(size.r),
+            c: self.c.rem_euclid(size.c),
+        }
+    }
+
+    pub fn neighbors4(self) -> impl Iterator<Item = Pos> {
+        Dir::ORTHOGONAL.into_iter().map(move |dir| self + dir)
+    }
+
+    pub fn neighbors8(self) -> impl Iterator<Item = Pos> {
+        Dir::ALL.into_iter().map(move |dir| self + dir)
+    }
+}
+
+impl From<(i32, i32)> for Pos {
+    fn from((r, c): (i32, i32)) -> Self {
+        Pos { r, c }
+    }
+}
+
+impl From<Pos> for (i32, i32) {
+    fn from(pos: Pos) -> Self {
+        (pos.r, pos.c)
+    }
+}
+
+impl From<Dir> for Pos {
+    fn from(dir: Dir) -> Self {
+        dir.delta()
+    }
+}
+
+impl Add for Pos {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Pos {
+            r: self.r + other.r,
+            c: self.c + other.c,
+        }
+    }
+}
+
+impl Add<Dir> for Pos {
+    type Output = Self;
+    fn add(self, dir: Dir) -> Self {
+        self + dir.delta()
+    }
+}
+
+impl AddAssign for Pos {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl AddAssign<Dir> for Pos {
+    fn add_assign(&mut self, dir: Dir) {
+        *self = *self + dir;
+    }
+}
+
+impl Sub for Pos {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Pos {
+            r: self.r - other.r,
+            c: self.c - other.c,
+        }
+    }
+}
+
+impl Sub<Dir> for Pos {
+    type Output = Self;
+    fn sub(self, dir: Dir) -> Self {
+        self - dir.delta()
+    }
+}
+
+impl SubAssign for Pos {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i32> for Pos {
+    type Output = Self;
+    fn mul(self, s: i32) -> Self {
+        Pos {
+            r: self.r * s,
+            c: self.c * s,
+        }
+    }
+}
+
+impl Neg for Pos {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Pos {
+            r: -self.r,
+            c: -self.c,
+        }
+    }
+}
+
+/// Component-wise `rem_euclid`, see [`Pos::wrap`].
+impl Rem for Pos {
+    type Output = Self;
+    fn rem(self, size: Self) -> Self {
+        self.wrap(size)
+    }
+}
+
+/// A compass direction, with rows growing downwards.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Dir {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir {
+    /// All directions, clockwise from `Up`.
+    pub const ALL: [Dir; 8] = [
+        Dir::Up,
+        Dir::UpRight,
+        Dir::Right,
+        Dir::DownRight,
+        Dir::Down,
+        Dir::DownLeft,
+        Dir::Left,
+        Dir::UpLeft,
+    ];
+    pub const ORTHOGONAL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+    pub const DIAGONAL: [Dir; 4] = [Dir::UpRight, Dir::DownRight, Dir::DownLeft, Dir::UpLeft];
+
+    pub fn delta(self) -> Pos {
+        match self {
+            Dir::Up => Pos::new(-1, 0),
+            Dir::UpRight => Pos::new(-1, 1),
+            Dir::Right => Pos::new(0, 1),
+            Dir::DownRight => Pos::new(1, 1),
+            Dir::Down => Pos::new(1, 0),
+            Dir::DownLeft => Pos::new(1, -1),
+            Dir::Left => Pos::new(0, -1),
+            Dir::UpLeft => Pos::new(-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Rotates clockwise by `eighths` eighths of a full turn, counter-clockwise if negative.
+    pub fn rotate(self, eighths: i32) -> Dir {
+        Dir::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    pub fn turn_right(self) -> Dir {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Dir {
+        self.rotate(-2)
+    }
+
+    pub fn reverse(self) -> Dir {
+        self.rotate(4)
+    }
+
+    /// Mirrors across the vertical axis, swapping left and right.
+    pub fn flip_horizontal(self) -> Dir {
+        Dir::ALL[(8 - self as usize) % 8]
+    }
+
+    /// Mirrors across the horizontal axis, swapping up and down.
+    pub fn flip_vertical(self) -> Dir {
+        Dir::ALL[(12 - self as usize) % 8]
+    }
+
+    /// Parses the `^`, `>`, `v` and `<` arrows.
+    pub fn from_arrow(ch: char) -> Option<Dir> {
+        match ch {
+            '^' => Some(Dir::Up),
+            '>' => Some(Dir::Right),
+            'v' => Some(Dir::Down),
+            '<' => Some(Dir::Left),
+            _ => None,
+        }
+    }
+
+    /// The arrow `from_arrow` parses, for the four orthogonal directions.
+    pub fn arrow(self) -> Option<char> {
+        match self {
+            Dir::Up => Some('^'),
+            Dir::Right => Some('>'),
+            Dir::Down => Some('v'),
+            Dir::Left => Some('<'),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<Pos> for Dir {
+    type Error = Pos;
+    fn try_from(delta: Pos) -> Result<Self, Pos> {
+        Dir::ALL
+            .into_iter()
+            .find(|dir| dir.delta() == delta)
+            .ok_or(delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chebyshev_takes_the_longer_axis() {
+        assert_eq!(Pos::new(1, -2).chebyshev(Pos::new(4, 3)), 5);
+        assert_eq!(Pos::new(-3, 0).chebyshev(Pos::ORIGIN), 3);
+        assert_eq!(Pos::new(2, 2).chebyshev(Pos::new(2, 2)), 0);
+    }
+
+    #[test]
+    fn diagonals_alternate() {
+        assert!(Dir::DIAGONAL.iter().all(|dir| dir.is_diagonal()));
+        assert!(!Dir::ORTHOGONAL.iter().any(|dir| dir.is_diagonal()));
+    }
+
+    #[test]
+    fn flips_mirror_the_delta() {
+        for dir in Dir::ALL {
+            let Pos { r, c } = dir.delta();
+            assert_eq!(dir.flip_horizontal().delta(), Pos::new(r, -c), "{dir:?}");
+            assert_eq!(dir.flip_vertical().delta(), Pos::new(-r, c), "{dir:?}");
+        }
+        assert_eq!(Dir::UpRight.flip_horizontal(), Dir::UpLeft);
+        assert_eq!(Dir::UpRight.flip_vertical(), Dir::DownRight);
+    }
+
+    #[test]
+    fn dir_from_delta() {
+        for dir in Dir::ALL {
+            assert_eq!(Dir::try_from(dir.delta()), Ok(dir));
+        }
+        assert_eq!(Dir::try_from(Pos::new(0, 2)), Err(Pos::new(0, 2)));
+        assert_eq!(Dir::try_from(Pos::ORIGIN), Err(Pos::ORIGIN));
+    }
+}
@@ -0,0 +1,244 @@
+use crate::{
+    geom::Pos,
+    parse::{self, Line, ParseError},
+};
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+/// A rectangular grid stored row by row in a flat buffer, addressed by [`Pos`] or `(row, column)`.
+///
+/// `get` returns `None` outside the grid, indexing panics there.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> Pos {
+        Pos::new(self.height as i32, self.width as i32)
+    }
+
+    pub fn contains(&self, pos: impl Into<Pos>) -> bool {
+        let Pos { r, c } = pos.into();
+        r >= 0 && c >= 0 && (r as usize) < self.height && (c as usize) < self.width
+    }
+
+    fn offset(&self, pos: impl Into<Pos>) -> Option<usize> {
+        let pos = pos.into();
+        self.contains(pos)
+            .then(|| pos.r as usize * self.width + pos.c as usize)
+    }
+
+    pub fn get(&self, pos: impl Into<Pos>) -> Option<&T> {
+        self.offset(pos).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, pos: impl Into<Pos>) -> Option<&mut T> {
+        self.offset(pos).map(|offset| &mut self.cells[offset])
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len())
+            .map(move |offset| Pos::new((offset / width) as i32, (offset % width) as i32))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The orthogonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        pos.neighbors4().filter(|&pos| self.contains(pos))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        pos.neighbors8().filter(|&pos| self.contains(pos))
+    }
+
+    /// The first position in row-major order holding `value`.
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .find(|(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.cells[r * self.width..(r + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, c: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(c).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|c| self.column(c))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses one row per line, turning each character into a cell with `cell`. `expected`
+    /// describes the characters `cell` accepts.
+    pub fn parse_with(
+        input: &str,
+        expected: &str,
+        cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        Grid::parse_lines(parse::lines(input), expected, cell)
+    }
+
+    /// Like [`Grid::parse_with`], for a grid embedded in a larger input.
+    pub fn parse_lines<'a>(
+        lines: impl IntoIterator<Item = Line<'a>>,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut rows = Vec::new();
+        let mut width = None;
+        for line in lines {
+            let mut row = Vec::new();
+            for (offset, ch) in line.text.char_indices() {
+                if Some(row.len()) == width {
+                    return Err(line.error(&line.text[offset..], "the end of the row"));
+                }
+                let at = &line.text[offset..offset + ch.len_utf8()];
+                row.push(cell(ch).ok_or_else(|| line.error(at, expected))?);
+            }
+            let width = *width.get_or_insert(row.len());
+            if row.len() < width {
+                return Err(line.end_error(format_args!("{} more cells", width - row.len())));
+            }
+            rows.push(row);
+        }
+        Ok(rows.into_iter().collect())
+    }
+}
+
+impl Grid<char> {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Grid::parse_with(input, "a map tile", Some)
+    }
+}
+
+/// Builds a grid from its rows. Panics if the rows differ in length.
+impl<T> FromIterator<Vec<T>> for Grid<T> {
+    fn from_iter<I: IntoIterator<Item = Vec<T>>>(rows: I) -> Self {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for row in rows {
+            let width = *width.get_or_insert(row.len());
+            assert_eq!(row.len(), width, "row {height} has a different width");
+            cells.extend(row);
+            height += 1;
+        }
+        Grid {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        }
+    }
+}
+
+impl<T, P: Into<Pos>> Index<P> for Grid<T> {
+    type Output = T;
+    fn index(&self, pos: P) -> &T {
+        let pos = pos.into();
+        match self.offset(pos) {
+            Some(offset) => &self.cells[offset],
+            None => panic!("{pos:?} is outside of the grid"),
+        }
+    }
+}
+
+impl<T, P: Into<Pos>> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut T {
+        let pos = pos.into();
+        match self.offset(pos) {
+            Some(offset) => &mut self.cells[offset],
+            None => panic!("{pos:?} is outside of the grid"),
+        }
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors8_stay_inside() {
+        let grid = Grid::new(3, 4, 0);
+        assert_eq!(grid.neighbors8(Pos::new(1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbors8(Pos::ORIGIN).collect::<Vec<_>>(),
+            [Pos::new(0, 1), Pos::new(1, 1), Pos::new(1, 0)]
+        );
+        assert_eq!(grid.neighbors8(Pos::new(2, 3)).count(), 3);
+    }
+
+    #[test]
+    fn wide_row_is_reported() {
+        let err = Grid::parse("ab\nabc\nab").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 3, "c"));
+        assert_eq!(err.expected, "the end of the row");
+    }
+
+    #[test]
+    fn short_row_is_reported() {
+        let err = Grid::parse("abc\nabc\na").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (3, 2, ""));
+        assert_eq!(err.expected, "2 more cells");
+    }
+}
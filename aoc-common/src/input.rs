@@ -0,0 +1,176 @@
+use anyhow::{bail, Context, Result};
+use std::{
+    env, fs,
+    io::{read_to_string, stdin, IsTerminal},
+    path::{Path, PathBuf},
+};
+
+/// The input cache, `inputs/` unless `AOC_INPUTS` points elsewhere.
+pub fn input_dir() -> PathBuf {
+    env::var_os("AOC_INPUTS").map_or_else(|| PathBuf::from("inputs"), PathBuf::from)
+}
+
+/// `dayNN.txt`, or `dayNN.example.txt` for the published example, inside `dir`.
+pub fn input_path(dir: &Path, day: u8, example: bool) -> PathBuf {
+    named_input_path(dir, day, if example { "example" } else { "input" })
+}
+
+/// Cached inputs are named `input` for `dayNN.txt` and `NAME` for `dayNN.NAME.txt`.
+pub fn named_input_path(dir: &Path, day: u8, name: &str) -> PathBuf {
+    if name == "input" {
+        dir.join(format!("day{day:02}.txt"))
+    } else {
+        dir.join(format!("day{day:02}.{name}.txt"))
+    }
+}
+
+/// The names of all inputs cached for `day`, sorted. A missing `dir` holds none.
+pub fn stored_inputs(dir: &Path, day: u8) -> Result<Vec<String>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+    let prefix = format!("day{day:02}");
+    let mut names = Vec::new();
+    for entry in entries {
+        let file_name = entry?.file_name();
+        let Some(rest) = file_name
+            .to_str()
+            .and_then(|name| name.strip_prefix(&prefix))
+            .and_then(|rest| rest.strip_suffix(".txt"))
+        else {
+            continue;
+        };
+        if rest.is_empty() {
+            names.push("input".to_string());
+        } else if let Some(name) = rest.strip_prefix('.') {
+            names.push(name.to_string());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+/// How the user asked for a day's input to be found.
+#[derive(Clone, Debug, Default)]
+pub struct InputArgs {
+    pub path: Option<PathBuf>,
+    pub example: bool,
+}
+
+impl InputArgs {
+    /// Parses the `[PATH] [--example]` arguments of the per-day binaries.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut input = InputArgs::default();
+        for arg in args {
+            match arg.as_str() {
+                "--example" => input.example = true,
+                _ if arg.starts_with('-') => bail!("unexpected argument {arg:?}"),
+                _ if input.path.is_none() => input.path = Some(arg.into()),
+                _ => bail!("more than one input path given"),
+            }
+        }
+        Ok(input)
+    }
+
+    /// Picks the explicit path, then the cached input, then stdin. `--example` swaps the
+    /// cached input for the cached example.
+    pub fn resolve(&self, dir: &Path, day: u8) -> Result<Source> {
+        if let Some(path) = &self.path {
+            if !path.exists() {
+                bail!("input file {} does not exist", path.display());
+            }
+            return Ok(Source::File(path.clone()));
+        }
+        if self.example {
+            let path = input_path(dir, day, true);
+            if !path.is_file() {
+                bail!("example input {} does not exist", path.display());
+            }
+            return Ok(Source::File(path));
+        }
+        let path = input_path(dir, day, false);
+        if path.is_file() {
+            return Ok(Source::File(path));
+        }
+        if stdin().is_terminal() {
+            bail!(
+                "no input for day {day}: {} does not exist and nothing was piped to stdin",
+                path.display()
+            );
+        }
+        Ok(Source::Stdin)
+    }
+
+    pub fn read(&self, day: u8) -> Result<String> {
+        match self.resolve(&input_dir(), day)? {
+            Source::File(path) => {
+                fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))
+            }
+            Source::Stdin => Ok(read_to_string(stdin())?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch_dir;
+
+    fn args(args: &[&str]) -> InputArgs {
+        InputArgs::parse(args.iter().map(|arg| arg.to_string())).unwrap()
+    }
+
+    #[test]
+    fn explicit_path_comes_first() {
+        let dir = scratch_dir("input-explicit");
+        let path = dir.join("mine.txt");
+        fs::write(&path, "1").unwrap();
+        fs::write(input_path(&dir, 3, false), "2").unwrap();
+        let source = args(&[path.to_str().unwrap(), "--example"]).resolve(&dir, 3);
+        assert_eq!(source.unwrap(), Source::File(path));
+    }
+
+    #[test]
+    fn missing_explicit_path_is_named() {
+        let dir = scratch_dir("input-missing");
+        let path = dir.join("nowhere.txt");
+        let err = args(&[path.to_str().unwrap()])
+            .resolve(&dir, 3)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("input file {} does not exist", path.display())
+        );
+    }
+
+    #[test]
+    fn cached_input() {
+        let dir = scratch_dir("input-cached");
+        let path = dir.join("day07.txt");
+        fs::write(&path, "1").unwrap();
+        assert_eq!(args(&[]).resolve(&dir, 7).unwrap(), Source::File(path));
+    }
+
+    #[test]
+    fn example_replaces_the_cached_input() {
+        let dir = scratch_dir("input-example");
+        fs::write(dir.join("day07.txt"), "1").unwrap();
+        let example = dir.join("day07.example.txt");
+        let err = args(&["--example"]).resolve(&dir, 7).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("example input {} does not exist", example.display())
+        );
+        fs::write(&example, "2").unwrap();
+        assert_eq!(
+            args(&["--example"]).resolve(&dir, 7).unwrap(),
+            Source::File(example)
+        );
+    }
+}
@@ -0,0 +1,30 @@
+//! Code shared between the daily solutions.
+
+pub mod bench;
+pub mod geom;
+pub mod grid;
+pub mod input;
+pub mod output;
+pub mod par;
+pub mod params;
+pub mod parse;
+pub mod render;
+pub mod search;
+pub mod solution;
+
+pub use geom::{Dir, Pos};
+pub use grid::Grid;
+pub use input::InputArgs;
+pub use output::{Answer, Format};
+pub use params::{Param, Params};
+pub use parse::ParseError;
+pub use solution::{exit_code, run, Day, Part, Solution};
+
+/// An empty directory under the system's temporary directory for a test to write into.
+#[cfg(test)]
+pub(crate) fn scratch_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-common-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
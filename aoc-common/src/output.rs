@@ -0,0 +1,127 @@
+use crate::Part;
+use anyhow::{bail, Result};
+use std::{fmt::Write, str::FromStr, time::Duration};
+
+/// One part's answer and how long computing it took, parsing excluded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: u8,
+    pub part: Part,
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+impl Answer {
+    /// A `{day, part, answer, elapsed_ns}` record.
+    pub fn json(&self) -> String {
+        format!(
+            r#"{{"day":{},"part":{},"answer":{},"elapsed_ns":{}}}"#,
+            self.day,
+            self.part,
+            json_string(&self.value),
+            self.elapsed.as_nanos()
+        )
+    }
+}
+
+/// How the runners print their results: `Part N: ANSWER` lines, or one JSON record per line.
+/// Diagnostics go to stderr either way.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl Format {
+    pub fn print(self, answer: &Answer) {
+        match self {
+            Format::Text => println!("Part {}: {}", answer.part, answer.value),
+            Format::Json => println!("{}", answer.json()),
+        }
+    }
+
+    /// Removes `--format FORMAT` from `args`, defaulting to text.
+    pub fn take(args: &mut Vec<String>) -> Result<Format> {
+        let Some(index) = args.iter().position(|arg| arg == "--format") else {
+            return Ok(Format::Text);
+        };
+        if index + 1 == args.len() {
+            bail!("--format needs a value");
+        }
+        let format = args[index + 1].parse()?;
+        args.drain(index..index + 2);
+        Ok(format)
+    }
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => bail!("format must be text or json, got {s:?}"),
+        }
+    }
+}
+
+/// `s` as a quoted JSON string.
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if ch.is_control() => write!(out, "\\u{:04x}", ch as u32).unwrap(),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_escapes() {
+        assert_eq!(json_string("plain"), r#""plain""#);
+        assert_eq!(json_string(r#"a "b" \c"#), r#""a \"b\" \\c""#);
+        assert_eq!(json_string("1\n2\r\t"), r#""1\n2\r\t""#);
+        assert_eq!(json_string("\u{0}\u{1b}é"), r#""\u0000\u001bé""#);
+    }
+
+    #[test]
+    fn json_record() {
+        let answer = Answer {
+            day: 7,
+            part: Part::Two,
+            value: "a\"b".to_string(),
+            elapsed: Duration::from_micros(1500),
+        };
+        assert_eq!(
+            answer.json(),
+            r#"{"day":7,"part":2,"answer":"a\"b","elapsed_ns":1500000}"#
+        );
+    }
+
+    #[test]
+    fn take_format() {
+        let mut args = vec!["--format".to_string(), "json".to_string(), "x".to_string()];
+        assert_eq!(Format::take(&mut args).unwrap(), Format::Json);
+        assert_eq!(args, ["x"]);
+        assert_eq!(Format::take(&mut args).unwrap(), Format::Text);
+        let mut args = vec!["--format".to_string(), "yaml".to_string()];
+        let err = Format::take(&mut args).unwrap_err();
+        assert_eq!(err.to_string(), "format must be text or json, got \"yaml\"");
+        let mut args = vec!["--format".to_string()];
+        let err = Format::take(&mut args).unwrap_err();
+        assert_eq!(err.to_string(), "--format needs a value");
+    }
+}
@@ -0,0 +1,35 @@
+//! `into_par_iter` and `par_iter`, which run on rayon's thread pool with the `parallel` feature
+//! and are plain sequential iterators without it. Code using them sticks to the adapters both
+//! kinds of iterator share, such as `map`, `filter`, `count`, `sum` and `collect`.
+
+#[cfg(feature = "parallel")]
+pub use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
+
+#[cfg(not(feature = "parallel"))]
+pub use serial::{IntoParallelIterator, IntoParallelRefIterator};
+
+#[cfg(not(feature = "parallel"))]
+mod serial {
+    pub trait IntoParallelIterator: IntoIterator + Sized {
+        fn into_par_iter(self) -> Self::IntoIter {
+            self.into_iter()
+        }
+    }
+
+    impl<I: IntoIterator> IntoParallelIterator for I {}
+
+    pub trait IntoParallelRefIterator<'a> {
+        type Iter: Iterator;
+        fn par_iter(&'a self) -> Self::Iter;
+    }
+
+    impl<'a, I: 'a + ?Sized> IntoParallelRefIterator<'a> for I
+    where
+        &'a I: IntoIterator,
+    {
+        type Iter = <&'a I as IntoIterator>::IntoIter;
+        fn par_iter(&'a self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+}
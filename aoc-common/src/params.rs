@@ -0,0 +1,152 @@
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+
+/// A constant of a puzzle that differs between the example and the real input, and that the
+/// runners can override with `--param name=value`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    /// The value for the real input.
+    pub default: i64,
+    /// The value the puzzle text uses for its example.
+    pub example: i64,
+}
+
+/// The value of each of a day's parameters.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<&'static str, i64>);
+
+impl Params {
+    /// Every parameter at its default, or at its example preset if `example`.
+    pub fn new(params: &[Param], example: bool) -> Self {
+        let value = |param: &Param| {
+            if example {
+                param.example
+            } else {
+                param.default
+            }
+        };
+        Params(
+            params
+                .iter()
+                .map(|param| (param.name, value(param)))
+                .collect(),
+        )
+    }
+
+    /// Applies a `name=value` override.
+    pub fn set(&mut self, assignment: &str) -> Result<()> {
+        let (name, value) = assignment
+            .split_once('=')
+            .with_context(|| format!("expected name=value, got {assignment:?}"))?;
+        let known = self.0.keys().copied().collect::<Vec<_>>().join(", ");
+        let Some(slot) = self.0.get_mut(name) else {
+            if known.is_empty() {
+                bail!("unknown parameter {name:?}: this day has no parameters");
+            }
+            bail!("unknown parameter {name:?}: expected one of {known}");
+        };
+        *slot = value
+            .parse()
+            .with_context(|| format!("parameter {name} must be an integer, got {value:?}"))?;
+        Ok(())
+    }
+
+    pub fn get<T: TryFrom<i64>>(&self, name: &str) -> Result<T> {
+        let value = *self
+            .0
+            .get(name)
+            .with_context(|| format!("no parameter named {name}"))?;
+        T::try_from(value)
+            .ok()
+            .with_context(|| format!("parameter {name} = {value} is out of range"))
+    }
+
+    /// Removes every `--param name=value` pair from `args`, returning the assignments.
+    pub fn take(args: &mut Vec<String>) -> Result<Vec<String>> {
+        let mut assignments = Vec::new();
+        while let Some(index) = args.iter().position(|arg| arg == "--param") {
+            if index + 1 == args.len() {
+                bail!("--param needs a value");
+            }
+            assignments.push(args.remove(index + 1));
+            args.remove(index);
+        }
+        Ok(assignments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param {
+            name: "size",
+            default: 71,
+            example: 7,
+        },
+        Param {
+            name: "steps",
+            default: 1024,
+            example: 12,
+        },
+    ];
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn presets_and_overrides() {
+        assert_eq!(Params::new(PARAMS, false).get::<i64>("size").unwrap(), 71);
+        let mut params = Params::new(PARAMS, true);
+        assert_eq!(params.get::<usize>("size").unwrap(), 7);
+        params.set("size=-3").unwrap();
+        assert_eq!(params.get::<i64>("size").unwrap(), -3);
+        assert_eq!(params.get::<i64>("steps").unwrap(), 12);
+    }
+
+    #[test]
+    fn bad_assignments() {
+        let mut params = Params::new(PARAMS, false);
+        let err = |params: &mut Params, assignment| params.set(assignment).unwrap_err().to_string();
+        assert_eq!(
+            err(&mut params, "width=3"),
+            "unknown parameter \"width\": expected one of size, steps"
+        );
+        assert_eq!(
+            err(&mut Params::default(), "width=3"),
+            "unknown parameter \"width\": this day has no parameters"
+        );
+        assert_eq!(
+            err(&mut params, "size=big"),
+            "parameter size must be an integer, got \"big\""
+        );
+        assert_eq!(
+            err(&mut params, "size"),
+            "expected name=value, got \"size\""
+        );
+        assert_eq!(params.get::<i64>("size").unwrap(), 71);
+    }
+
+    #[test]
+    fn out_of_range() {
+        let mut params = Params::new(PARAMS, false);
+        params.set("size=-1").unwrap();
+        let err = params.get::<usize>("size").unwrap_err();
+        assert_eq!(err.to_string(), "parameter size = -1 is out of range");
+        let err = params.get::<usize>("width").unwrap_err();
+        assert_eq!(err.to_string(), "no parameter named width");
+    }
+
+    #[test]
+    fn take_removes_each_pair() {
+        let mut args = strings(&["--param", "size=7", "input.txt", "--param", "steps=1"]);
+        assert_eq!(Params::take(&mut args).unwrap(), ["size=7", "steps=1"]);
+        assert_eq!(args, ["input.txt"]);
+        let mut args = strings(&["input.txt", "--param"]);
+        let err = Params::take(&mut args).unwrap_err();
+        assert_eq!(err.to_string(), "--param needs a value");
+    }
+}
@@ -0,0 +1,177 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    iter::Enumerate,
+    str::FromStr,
+};
+
+/// A malformed puzzle input, pointing at the offending text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Numbered from 1.
+    pub line: usize,
+    /// Numbered from 1, in characters.
+    pub column: usize,
+    pub expected: String,
+    /// Empty at the end of a line or of the input.
+    pub found: String,
+    /// The whole offending line.
+    pub text: String,
+}
+
+impl ParseError {
+    /// The offending line with the bad text underlined, for printing below the message.
+    pub fn snippet(&self) -> String {
+        let gutter = self.line.to_string().len();
+        let width = self.found.chars().count().max(1);
+        format!(
+            "{:gutter$} |\n{} | {}\n{:gutter$} | {:indent$}{}",
+            "",
+            self.line,
+            self.text,
+            "",
+            "",
+            "^".repeat(width),
+            indent = self.column - 1,
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, "nothing")
+        } else {
+            write!(f, "{:?}", self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// One line of the input. The methods take slices of `text` and report errors at their position.
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    /// Numbered from 1.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// An error at `at`, which should be a slice of this line; other strings are reported at
+    /// its start.
+    pub fn error(&self, at: &str, expected: impl Display) -> ParseError {
+        let start = self.text.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset <= self.text.len())
+            .unwrap_or(0);
+        ParseError {
+            line: self.number,
+            column: self.text[..offset].chars().count() + 1,
+            expected: expected.to_string(),
+            found: at.to_string(),
+            text: self.text.to_string(),
+        }
+    }
+
+    /// An error just past the end of the line.
+    pub fn end_error(&self, expected: impl Display) -> ParseError {
+        self.error(&self.text[self.text.len()..], expected)
+    }
+
+    pub fn parse<T: FromStr>(&self, at: &'a str, expected: impl Display) -> Result<T, ParseError> {
+        at.parse().map_err(|_| self.error(at, expected))
+    }
+
+    pub fn number<T: FromStr>(&self, at: &'a str) -> Result<T, ParseError> {
+        self.parse(at, "a number")
+    }
+
+    pub fn split_once(&self, at: &'a str, sep: &str) -> Result<(&'a str, &'a str), ParseError> {
+        at.split_once(sep)
+            .ok_or_else(|| self.error(&at[at.len()..], format!("{sep:?}")))
+    }
+
+    pub fn strip_prefix(&self, at: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        at.strip_prefix(prefix)
+            .ok_or_else(|| self.error(at, format!("{prefix:?}")))
+    }
+
+    /// Fails unless `at` is empty, so that nothing trails a parsed value.
+    pub fn end(&self, at: &'a str) -> Result<(), ParseError> {
+        if at.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(at, "the end of the line"))
+        }
+    }
+}
+
+/// The numbered lines of an input.
+#[derive(Clone, Debug)]
+pub struct Lines<'a> {
+    lines: Enumerate<std::str::Lines<'a>>,
+    last: Option<Line<'a>>,
+}
+
+impl<'a> Lines<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Lines {
+            lines: input.lines().enumerate(),
+            last: None,
+        }
+    }
+
+    /// The next line, or an error describing what a truncated input is missing.
+    pub fn expect(&mut self, expected: impl Display) -> Result<Line<'a>, ParseError> {
+        self.next().ok_or_else(|| ParseError {
+            line: self.last.map_or(0, |line| line.number) + 1,
+            column: 1,
+            expected: expected.to_string(),
+            found: String::new(),
+            text: String::new(),
+        })
+    }
+
+    /// An error just past the end of the last line read, for something the lines so far should
+    /// have contained.
+    pub fn end_error(&self, expected: impl Display) -> ParseError {
+        match self.last {
+            Some(line) => line.end_error(expected),
+            None => Line {
+                number: 1,
+                text: "",
+            }
+            .end_error(expected),
+        }
+    }
+
+    /// The lines up to the next blank line, which is skipped.
+    pub fn block(&mut self) -> impl Iterator<Item = Line<'a>> + '_ {
+        self.by_ref().take_while(|line| !line.text.is_empty())
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Line<'a>> {
+        let (index, text) = self.lines.next()?;
+        let line = Line {
+            number: index + 1,
+            text,
+        };
+        self.last = Some(line);
+        Some(line)
+    }
+}
+
+pub fn lines(input: &str) -> Lines<'_> {
+    Lines::new(input)
+}
@@ -0,0 +1,249 @@
+//! Drawing grids as images, one square block of pixels per cell, and saving runs as numbered
+//! frames. `ffmpeg -i frame-%05d.png run.gif` turns a directory of frames into an animation.
+
+use crate::{Grid, Pos};
+use anyhow::{bail, Context, Result};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    iter,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const GREY: Rgb = [96, 96, 96];
+pub const RED: Rgb = [220, 50, 47];
+pub const GREEN: Rgb = [80, 200, 80];
+pub const BLUE: Rgb = [38, 139, 210];
+pub const YELLOW: Rgb = [240, 200, 40];
+pub const BROWN: Rgb = [150, 100, 50];
+
+/// One image, in cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    cells: Grid<Rgb>,
+}
+
+impl Frame {
+    pub fn new(height: usize, width: usize, background: Rgb) -> Self {
+        Frame {
+            cells: Grid::new(height, width, background),
+        }
+    }
+
+    /// `grid` with each cell coloured by `color`.
+    pub fn from_grid<T>(grid: &Grid<T>, color: impl FnMut(&T) -> Rgb) -> Self {
+        Frame {
+            cells: grid.map(color),
+        }
+    }
+
+    /// Colours the cell at `pos`, if it is inside the frame.
+    pub fn paint(&mut self, pos: Pos, color: Rgb) {
+        if let Some(cell) = self.cells.get_mut(pos) {
+            *cell = color;
+        }
+    }
+
+    pub fn paint_all(&mut self, positions: impl IntoIterator<Item = Pos>, color: Rgb) {
+        for pos in positions {
+            self.paint(pos, color);
+        }
+    }
+
+    /// Writes the frame with every cell drawn as a `scale` by `scale` square.
+    pub fn save(&self, path: &Path, format: ImageFormat, scale: usize) -> Result<()> {
+        let (width, height) = (self.cells.width() * scale, self.cells.height() * scale);
+        let mut pixels = Vec::with_capacity(width * height * 3);
+        for row in self.cells.rows() {
+            let line = row
+                .iter()
+                .flat_map(|&color| iter::repeat_n(color, scale))
+                .flatten()
+                .collect::<Vec<_>>();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        let file =
+            File::create(path).with_context(|| format!("cannot create {}", path.display()))?;
+        let mut out = BufWriter::new(file);
+        match format {
+            ImageFormat::Ppm => {
+                write!(out, "P6\n{width} {height}\n255\n")?;
+                out.write_all(&pixels)?;
+            }
+            ImageFormat::Png => {
+                let mut encoder = png::Encoder::new(&mut out, width as u32, height as u32);
+                encoder.set_color(png::ColorType::Rgb);
+                encoder.set_depth(png::BitDepth::Eight);
+                encoder.write_header()?.write_image_data(&pixels)?;
+            }
+        }
+        out.flush()?;
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    #[default]
+    Png,
+}
+
+impl ImageFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+impl FromStr for ImageFormat {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "ppm" => Ok(ImageFormat::Ppm),
+            "png" => Ok(ImageFormat::Png),
+            _ => bail!("image format must be ppm or png, got {s:?}"),
+        }
+    }
+}
+
+/// Saves frames as `frame-00000.png`, `frame-00001.png` and so on into a directory, keeping
+/// only every `every`th frame offered so that long runs stay a manageable size.
+#[derive(Debug)]
+pub struct Recorder {
+    dir: PathBuf,
+    format: ImageFormat,
+    scale: usize,
+    every: usize,
+    offered: usize,
+    saved: usize,
+    /// Whether the last frame offered was kept.
+    kept_last: bool,
+}
+
+impl Recorder {
+    /// Creates `dir` if needed.
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        format: ImageFormat,
+        scale: usize,
+        every: usize,
+    ) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).with_context(|| format!("cannot create {}", dir.display()))?;
+        Ok(Recorder {
+            dir,
+            format,
+            scale: scale.max(1),
+            every: every.max(1),
+            offered: 0,
+            saved: 0,
+            kept_last: false,
+        })
+    }
+
+    /// Offers the next frame of the run. `frame` is only called for frames that are kept.
+    pub fn record(&mut self, frame: impl FnOnce() -> Frame) -> Result<()> {
+        self.kept_last = self.offered.is_multiple_of(self.every);
+        self.offered += 1;
+        if self.kept_last {
+            self.write(&frame())?;
+        }
+        Ok(())
+    }
+
+    /// Ends a run with `frame`, unless it was the last frame offered and already kept.
+    pub fn finish(&mut self, frame: impl FnOnce() -> Frame) -> Result<()> {
+        if self.kept_last {
+            return Ok(());
+        }
+        self.write(&frame())
+    }
+
+    /// Saves `frame` regardless of `every`, such as a single picture summing up a run.
+    pub fn save(&mut self, frame: &Frame) -> Result<()> {
+        self.kept_last = false;
+        self.write(frame)
+    }
+
+    fn write(&mut self, frame: &Frame) -> Result<()> {
+        let name = format!("frame-{:05}.{}", self.saved, self.format.extension());
+        frame.save(&self.dir.join(name), self.format, self.scale)?;
+        self.saved += 1;
+        Ok(())
+    }
+
+    /// The number of frames written so far.
+    pub fn saved(&self) -> usize {
+        self.saved
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch_dir;
+
+    #[test]
+    fn ppm_scales_each_cell() {
+        let dir = scratch_dir("render-ppm");
+        let mut frame = Frame::new(1, 2, RED);
+        frame.paint(Pos::new(0, 1), BLUE);
+        frame.paint(Pos::new(5, 5), GREEN);
+        let path = dir.join("frame.ppm");
+        frame.save(&path, ImageFormat::Ppm, 2).unwrap();
+        let row = [RED, RED, BLUE, BLUE].concat();
+        let expected = [b"P6\n4 2\n255\n".as_slice(), &row, &row].concat();
+        assert_eq!(fs::read(&path).unwrap(), expected);
+    }
+
+    #[test]
+    fn recorder_keeps_every_nth_frame() {
+        let dir = scratch_dir("render-every");
+        let mut recorder = Recorder::new(&dir, ImageFormat::Ppm, 1, 3).unwrap();
+        let mut drawn = Vec::new();
+        for n in 0..8 {
+            recorder
+                .record(|| {
+                    drawn.push(n);
+                    Frame::new(1, 1, BLACK)
+                })
+                .unwrap();
+        }
+        assert_eq!(drawn, [0, 3, 6]);
+        recorder.finish(|| Frame::new(1, 1, WHITE)).unwrap();
+        assert_eq!(recorder.saved(), 4);
+        assert_eq!(fs::read(dir.join("frame-00003.ppm")).unwrap()[11..], WHITE);
+        recorder.save(&Frame::new(1, 1, RED)).unwrap();
+        assert_eq!(recorder.saved(), 5);
+        assert!(dir.join("frame-00004.ppm").exists());
+        assert!(!dir.join("frame-00005.ppm").exists());
+    }
+
+    #[test]
+    fn finish_skips_a_kept_last_frame() {
+        let dir = scratch_dir("render-finish");
+        let mut recorder = Recorder::new(&dir, ImageFormat::Ppm, 1, 3).unwrap();
+        for _ in 0..7 {
+            recorder.record(|| Frame::new(1, 1, BLACK)).unwrap();
+        }
+        recorder.finish(|| unreachable!()).unwrap();
+        assert_eq!(recorder.saved(), 3);
+        recorder.save(&Frame::new(1, 1, RED)).unwrap();
+        recorder.finish(|| Frame::new(1, 1, WHITE)).unwrap();
+        assert_eq!(recorder.saved(), 5);
+    }
+}
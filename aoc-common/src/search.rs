@@ -0,0 +1,252 @@
+//! Shortest paths over graphs given implicitly by a `neighbors` callback, for any hashable state.
+//!
+//! Weighted searches take `neighbors(&state) -> impl IntoIterator<Item = (state, cost)>`; the
+//! breadth-first search takes plain states, each one step away.
+
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Edge costs: summed along a path and compared, with `C::default()` as zero.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// Everything a search reached: the cheapest cost of each state and, as a DAG, every
+/// predecessor through which that cost is achieved.
+#[derive(Clone, Debug)]
+pub struct Paths<S, C> {
+    pub costs: HashMap<S, C>,
+    pub predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash, C: Cost> Paths<S, C> {
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    /// One cheapest path from the start to `state`, both included.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.costs.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(previous) = self.predecessors.get(path.last()?).and_then(|p| p.first()) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every state lying on some cheapest path from the start to one of `ends`, ends included.
+    pub fn states_on_paths(&self, ends: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let mut stack = ends
+            .into_iter()
+            .filter(|end| self.costs.contains_key(end))
+            .collect::<Vec<_>>();
+        let mut seen = stack.iter().cloned().collect::<HashSet<_>>();
+        while let Some(state) = stack.pop() {
+            for previous in self.predecessors.get(&state).into_iter().flatten() {
+                if seen.insert(previous.clone()) {
+                    stack.push(previous.clone());
+                }
+            }
+        }
+        seen
+    }
+
+    /// Records reaching `state` from `from` at `cost`; true if that is a new best.
+    fn relax(&mut self, state: S, from: &S, cost: C) -> bool {
+        match self.costs.entry(state.clone()) {
+            Entry::Occupied(mut best) if cost < *best.get() => {
+                best.insert(cost);
+                self.predecessors.insert(state, vec![from.clone()]);
+                true
+            }
+            Entry::Occupied(best) => {
+                if cost == *best.get() {
+                    self.predecessors
+                        .entry(state)
+                        .or_default()
+                        .push(from.clone());
+                }
+                false
+            }
+            Entry::Vacant(best) => {
+                best.insert(cost);
+                self.predecessors.insert(state, vec![from.clone()]);
+                true
+            }
+        }
+    }
+
+    fn new(start: S) -> Self {
+        Paths {
+            costs: HashMap::from([(start, C::default())]),
+            predecessors: HashMap::new(),
+        }
+    }
+}
+
+/// Breadth-first search from `start`, where every move costs one step.
+pub fn bfs<S, I>(start: S, mut neighbors: impl FnMut(&S) -> I) -> Paths<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = Paths::new(start.clone());
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((state, steps)) = queue.pop_front() {
+        for next in neighbors(&state) {
+            if paths.relax(next.clone(), &state, steps + 1) {
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    paths
+}
+
+/// Dijkstra's algorithm from `start`, exploring everything reachable. Costs must be positive.
+pub fn dijkstra<S, C, I>(start: S, mut neighbors: impl FnMut(&S) -> I) -> Paths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut paths = Paths::new(start.clone());
+    let mut queue = BinaryHeap::from([Queued::new(C::default(), C::default(), start)]);
+    while let Some(Queued { cost, state, .. }) = queue.pop() {
+        if paths.costs[&state] < cost {
+            continue;
+        }
+        for (next, step) in neighbors(&state) {
+            if paths.relax(next.clone(), &state, cost + step) {
+                queue.push(Queued::new(cost + step, cost + step, next));
+            }
+        }
+    }
+    paths
+}
+
+/// A* from `start` to the first state satisfying `is_goal`, returning its cost and one cheapest
+/// path to it. `heuristic` must never overestimate the remaining cost, or the path found may not
+/// be the cheapest.
+pub fn astar<S, C, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut paths = Paths::new(start.clone());
+    let mut queue = BinaryHeap::from([Queued::new(heuristic(&start), C::default(), start)]);
+    while let Some(Queued { cost, state, .. }) = queue.pop() {
+        if paths.costs[&state] < cost {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some((cost, paths.path_to(&state)?));
+        }
+        for (next, step) in neighbors(&state) {
+            if paths.relax(next.clone(), &state, cost + step) {
+                let priority = cost + step + heuristic(&next);
+                queue.push(Queued::new(priority, cost + step, next));
+            }
+        }
+    }
+    None
+}
+
+/// A state waiting in the priority queue, ordered so that the lowest priority pops first.
+struct Queued<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C> Queued<S, C> {
+    fn new(priority: C, cost: C, state: S) -> Self {
+        Queued {
+            priority,
+            cost,
+            state,
+        }
+    }
+}
+
+impl<S, C: Ord> Ord for Queued<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Queued<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Queued<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Queued<S, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two equally short ways from 0 to 3, then on to 4, and a dead end at 5.
+    fn diamond(state: &u32) -> Vec<u32> {
+        match state {
+            0 => vec![1, 2, 5],
+            1 | 2 => vec![3],
+            3 => vec![4],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs_counts_steps() {
+        let paths = bfs(0, diamond);
+        assert_eq!(paths.cost(&4), Some(3));
+        assert_eq!(paths.cost(&5), Some(1));
+        assert_eq!(paths.cost(&6), None);
+        assert_eq!(paths.path_to(&4), Some(vec![0, 1, 3, 4]));
+        assert_eq!(paths.path_to(&0), Some(vec![0]));
+    }
+
+    #[test]
+    fn tied_predecessors_are_all_on_paths() {
+        let paths = bfs(0, diamond);
+        assert_eq!(paths.predecessors[&3], [1, 2]);
+        assert_eq!(paths.states_on_paths([4]), HashSet::from([0, 1, 2, 3, 4]));
+        assert_eq!(paths.states_on_paths([5, 6]), HashSet::from([0, 5]));
+    }
+
+    #[test]
+    fn dijkstra_keeps_only_the_cheapest_ties() {
+        let paths = dijkstra(0, |&state| match state {
+            0 => vec![(1, 1), (2, 2), (3, 3)],
+            1 => vec![(3, 2)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        });
+        assert_eq!(paths.cost(&3), Some(3));
+        assert_eq!(paths.states_on_paths([3]), HashSet::from([0, 1, 2, 3]));
+        let paths = dijkstra(0, |&state| match state {
+            0 => vec![(1, 1), (2, 1)],
+            1 => vec![(3, 1)],
+            2 => vec![(3, 5)],
+            _ => vec![],
+        });
+        assert_eq!(paths.states_on_paths([3]), HashSet::from([0, 1, 3]));
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{
+    bench::{self, Phase, Runs},
+    input::InputArgs,
+    output::{Answer, Format},
+    params::{Param, Params},
+    parse::ParseError,
+    render::Recorder,
+};
+use anyhow::{bail, Result};
+use std::{
+    env,
+    fmt::{self, Display},
+    process::ExitCode,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// One day of the calendar: how to parse the puzzle input and how to answer both parts.
+pub trait Solution {
+    const DAY: u8;
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+    /// The parts this day has; only day 25 lacks a second one.
+    const PARTS: &'static [Part] = &Part::BOTH;
+    /// The constants the puzzle sets differently for its example, such as a grid size.
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+
+    /// [`Solution::part1`] with the given [`Solution::PARAMS`]. Days with parameters implement
+    /// this and have `part1` call it with the defaults.
+    fn part1_with(input: &Self::Input, params: &Params) -> Result<Self::Answer1> {
+        let _ = params;
+        Self::part1(input)
+    }
+
+    /// [`Solution::part2`] with the given [`Solution::PARAMS`], see [`Solution::part1_with`].
+    fn part2_with(input: &Self::Input, params: &Params) -> Result<Self::Answer2> {
+        let _ = params;
+        Self::part2(input)
+    }
+
+    /// Draws the day's run as frames, for days with something to show.
+    fn render(input: &Self::Input, params: &Params, recorder: &mut Recorder) -> Result<()> {
+        let _ = (input, params, recorder);
+        bail!("day {} has no renderer", Self::DAY)
+    }
+
+    /// The parameters at their defaults for the real input.
+    fn defaults() -> Params {
+        Params::new(Self::PARAMS, false)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => bail!("part must be 1 or 2, got {s:?}"),
+        }
+    }
+}
+
+/// Object-safe view of a [`Solution`], so the runner can keep every day in one table.
+pub trait Day: Sync {
+    fn day(&self) -> u8;
+    fn parts(&self) -> &'static [Part];
+    fn params(&self) -> &'static [Param];
+    /// Parses `input` once and answers the requested parts in order.
+    fn run(&self, input: &str, parts: &[Part], params: &Params) -> Result<Vec<Answer>>;
+    /// Parses `input` and draws it, see [`Solution::render`].
+    fn render(&self, input: &str, params: &Params, recorder: &mut Recorder) -> Result<()>;
+    /// Times one phase on `input`, see [`bench::sample`].
+    fn time(&self, input: &str, phase: Phase, runs: Runs, params: &Params)
+        -> Result<Vec<Duration>>;
+}
+
+impl<S: Solution + Sync> Day for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        S::PARTS
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+
+    fn run(&self, input: &str, parts: &[Part], params: &Params) -> Result<Vec<Answer>> {
+        let input = S::parse(input)?;
+        parts
+            .iter()
+            .map(|&part| answer::<S>(&input, part, params))
+            .collect()
+    }
+
+    fn render(&self, input: &str, params: &Params, recorder: &mut Recorder) -> Result<()> {
+        S::render(&S::parse(input)?, params, recorder)
+    }
+
+    fn time(
+        &self,
+        input: &str,
+        phase: Phase,
+        runs: Runs,
+        params: &Params,
+    ) -> Result<Vec<Duration>> {
+        bench::sample::<S>(input, phase, runs, params)
+    }
+}
+
+fn answer<S: Solution>(input: &S::Input, part: Part, params: &Params) -> Result<Answer> {
+    let start = Instant::now();
+    let value = match part {
+        Part::One => S::part1_with(input, params)?.to_string(),
+        Part::Two => S::part2_with(input, params)?.to_string(),
+    };
+    Ok(Answer {
+        day: S::DAY,
+        part,
+        value,
+        elapsed: start.elapsed(),
+    })
+}
+
+/// Entry point of the per-day binaries: solves every part for the input picked by
+/// `[PATH] [--example] [--format text|json] [--param name=value]...`, see
+/// [`InputArgs::resolve`]. `--example` also switches the parameters to their example presets.
+pub fn run<S: Solution>() -> ExitCode {
+    exit_code(solve::<S>())
+}
+
+fn solve<S: Solution>() -> Result<()> {
+    let mut args = env::args().skip(1).collect();
+    let format = Format::take(&mut args)?;
+    let assignments = Params::take(&mut args)?;
+    let input_args = InputArgs::parse(args)?;
+    let mut params = Params::new(S::PARAMS, input_args.example);
+    for assignment in &assignments {
+        params.set(assignment)?;
+    }
+    let input = S::parse(&input_args.read(S::DAY)?)?;
+    for &part in S::PARTS {
+        format.print(&answer::<S>(&input, part, &params)?);
+    }
+    Ok(())
+}
+
+/// Prints a failed run's error to stderr, with the offending line for a [`ParseError`].
+pub fn exit_code(result: Result<()>) -> ExitCode {
+    let Err(err) = result else {
+        return ExitCode::SUCCESS;
+    };
+    eprintln!("error: {err:#}");
+    if let Some(err) = err.chain().find_map(|err| err.downcast_ref::<ParseError>()) {
+        eprintln!("{}", err.snippet());
+    }
+    ExitCode::FAILURE
+}
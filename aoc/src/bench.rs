@@ -0,0 +1,122 @@
+use crate::{table::table, verify::panic_message};
+use aoc_common::{
+    bench::{Phase, Runs},
+    output::json_string,
+    Day, Params,
+};
+use std::{
+    fmt::Write,
+    panic::{self, AssertUnwindSafe},
+    time::Duration,
+};
+
+/// The run times of one phase of one day.
+#[derive(Clone, Debug)]
+pub struct Timing {
+    pub day: u8,
+    pub phase: Phase,
+    /// Sorted, or why the phase could not be timed.
+    pub samples: Result<Vec<Duration>, String>,
+}
+
+impl Timing {
+    pub fn median(&self) -> Option<Duration> {
+        self.percentile(50)
+    }
+
+    pub fn p95(&self) -> Option<Duration> {
+        self.percentile(95)
+    }
+
+    /// A `{day, phase, runs, median_ns, p95_ns}` record, or `{day, phase, error}`.
+    pub fn json(&self) -> String {
+        let (day, phase) = (self.day, json_string(&self.phase.to_string()));
+        match &self.samples {
+            Ok(samples) => format!(
+                r#"{{"day":{day},"phase":{phase},"runs":{},"median_ns":{},"p95_ns":{}}}"#,
+                samples.len(),
+                self.median().unwrap_or_default().as_nanos(),
+                self.p95().unwrap_or_default().as_nanos(),
+            ),
+            Err(err) => format!(
+                r#"{{"day":{day},"phase":{phase},"error":{}}}"#,
+                json_string(err)
+            ),
+        }
+    }
+
+    /// The nearest-rank percentile.
+    fn percentile(&self, percent: usize) -> Option<Duration> {
+        let samples = self.samples.as_ref().ok()?;
+        let rank = (samples.len() * percent).div_ceil(100).max(1);
+        samples.get(rank - 1).copied()
+    }
+}
+
+/// Times each of `phases` of `day` on `input`.
+pub fn bench(
+    day: &dyn Day,
+    input: &str,
+    phases: &[Phase],
+    runs: Runs,
+    params: &Params,
+) -> Vec<Timing> {
+    phases
+        .iter()
+        .map(|&phase| {
+            let time = || day.time(input, phase, runs, params);
+            let result = panic::catch_unwind(AssertUnwindSafe(time));
+            let samples = match result {
+                Ok(Ok(mut samples)) => {
+                    samples.sort();
+                    Ok(samples)
+                }
+                Ok(Err(err)) => Err(format!("{err:#}")),
+                Err(payload) => Err(panic_message(payload)),
+            };
+            Timing {
+                day: day.day(),
+                phase,
+                samples,
+            }
+        })
+        .collect()
+}
+
+/// Lays the timings out as a table, one row per day and phase, with a summary line.
+pub fn report(timings: &[Timing]) -> String {
+    let header = ["day", "phase", "runs", "median", "p95", "error"];
+    let duration = |duration: Option<Duration>| {
+        duration.map_or_else(String::new, |duration| format!("{duration:.1?}"))
+    };
+    let rows = timings
+        .iter()
+        .map(|timing| {
+            let (runs, error) = match &timing.samples {
+                Ok(samples) => (samples.len().to_string(), String::new()),
+                Err(err) => (String::new(), err.clone()),
+            };
+            [
+                format!("{:02}", timing.day),
+                timing.phase.to_string(),
+                runs,
+                duration(timing.median()),
+                duration(timing.p95()),
+                error,
+            ]
+        })
+        .collect::<Vec<_>>();
+    let mut out = table(header, &rows);
+    let failed = timings
+        .iter()
+        .filter(|timing| timing.samples.is_err())
+        .count();
+    writeln!(
+        out,
+        "{} phases timed, {failed} failed, {:.1?} in total by median",
+        timings.len() - failed,
+        timings.iter().filter_map(Timing::median).sum::<Duration>(),
+    )
+    .unwrap();
+    out
+}
@@ -0,0 +1,6 @@
+//! Runs the daily solutions by number and checks them against known answers.
+
+pub mod bench;
+pub mod days;
+mod table;
+pub mod verify;
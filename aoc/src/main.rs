@@ -0,0 +1,296 @@
+use anyhow::{anyhow, bail, Context, Result};
+use aoc::{
+    bench, days,
+    verify::{self, Answers},
+};
+use aoc_common::{
+    bench::{Phase, Runs},
+    input,
+    render::{ImageFormat, Recorder},
+    Day, Format, InputArgs, Params, Part,
+};
+use std::{env, fs, path::PathBuf, process::ExitCode};
+
+const USAGE: &str = "usage:
+    aoc run --day N [--part 1|2] [--input PATH] [--example] [--param NAME=VALUE]...
+            [--format text|json]
+    aoc verify [--day N] [--answers PATH]
+    aoc bench [--day N] [--part 1|2] [--input PATH] [--example] [--param NAME=VALUE]...
+              [--warmup N] [--runs N] [--format text|json]
+    aoc render --day N --out DIR [--input PATH] [--example] [--param NAME=VALUE]...
+               [--image png|ppm] [--scale N] [--every N]
+
+--example reads inputs/dayNN.example.txt and sets the parameters to the example's presets;
+--param overrides one of them. render writes numbered frames into DIR, keeping every Nth
+frame with --every and drawing each cell as N by N pixels with --scale.";
+
+struct RunArgs {
+    day: u8,
+    /// `None` runs every part the day has.
+    parts: Option<Vec<Part>>,
+    input: InputArgs,
+    /// `--param` assignments, applied over the defaults or the example presets.
+    params: Vec<String>,
+    format: Format,
+}
+
+impl RunArgs {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut day = None;
+        let mut parts = None;
+        let mut input = InputArgs::default();
+        let mut params = Vec::new();
+        let mut format = Format::default();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().with_context(|| format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--day" => day = Some(value()?.parse().context("--day must be a number")?),
+                "--part" => parts = Some(vec![value()?.parse()?]),
+                "--input" => input.path = Some(value()?.into()),
+                "--example" => input.example = true,
+                "--param" => params.push(value()?),
+                "--format" => format = value()?.parse()?,
+                _ => bail!("unexpected argument {arg:?}\n{USAGE}"),
+            }
+        }
+        Ok(RunArgs {
+            day: day.with_context(|| format!("--day is required\n{USAGE}"))?,
+            parts,
+            input,
+            params,
+            format,
+        })
+    }
+}
+
+fn run(args: RunArgs) -> Result<()> {
+    let day = days::find(args.day).with_context(|| format!("day {} is not solved", args.day))?;
+    let input = args.input.read(args.day)?;
+    let parts = args.parts.as_deref().unwrap_or(day.parts());
+    let params = params(day, args.input.example, &args.params)?;
+    for answer in day.run(&input, parts, &params)? {
+        args.format.print(&answer);
+    }
+    Ok(())
+}
+
+fn params(day: &dyn Day, example: bool, assignments: &[String]) -> Result<Params> {
+    let mut params = Params::new(day.params(), example);
+    for assignment in assignments {
+        params
+            .set(assignment)
+            .with_context(|| format!("invalid --param for day {}", day.day()))?;
+    }
+    Ok(params)
+}
+
+struct VerifyArgs {
+    day: Option<u8>,
+    answers: PathBuf,
+}
+
+impl VerifyArgs {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut verify = VerifyArgs {
+            day: None,
+            answers: PathBuf::from("answers.toml"),
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().with_context(|| format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--day" => verify.day = Some(value()?.parse().context("--day must be a number")?),
+                "--answers" => verify.answers = value()?.into(),
+                _ => bail!("unexpected argument {arg:?}\n{USAGE}"),
+            }
+        }
+        Ok(verify)
+    }
+}
+
+fn verify(args: VerifyArgs) -> Result<()> {
+    let answers = Answers::load(&args.answers)?;
+    let days = match args.day {
+        Some(day) => vec![days::find(day).with_context(|| format!("day {day} is not solved"))?],
+        None => days::DAYS.to_vec(),
+    };
+    let checks = verify::verify(&days, &answers, &input::input_dir())?;
+    print!("{}", verify::report(&checks));
+    let failures = checks.iter().filter(|check| check.is_failure()).count();
+    if failures > 0 {
+        bail!("{failures} answers do not match");
+    }
+    Ok(())
+}
+
+struct BenchArgs {
+    day: Option<u8>,
+    /// `None` times every part each day has.
+    parts: Option<Vec<Part>>,
+    input: InputArgs,
+    params: Vec<String>,
+    runs: Runs,
+    format: Format,
+}
+
+impl BenchArgs {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut bench = BenchArgs {
+            day: None,
+            parts: None,
+            input: InputArgs::default(),
+            params: Vec::new(),
+            runs: Runs::default(),
+            format: Format::default(),
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().with_context(|| format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--day" => bench.day = Some(value()?.parse().context("--day must be a number")?),
+                "--part" => bench.parts = Some(vec![value()?.parse()?]),
+                "--input" => bench.input.path = Some(value()?.into()),
+                "--example" => bench.input.example = true,
+                "--param" => bench.params.push(value()?),
+                "--format" => bench.format = value()?.parse()?,
+                "--warmup" => {
+                    bench.runs.warmup = value()?.parse().context("--warmup must be a number")?
+                }
+                "--runs" => {
+                    bench.runs.samples = value()?.parse().context("--runs must be a number")?
+                }
+                _ => bail!("unexpected argument {arg:?}\n{USAGE}"),
+            }
+        }
+        if bench.runs.samples == 0 {
+            bail!("--runs must be at least 1");
+        }
+        if bench.input.path.is_some() && bench.day.is_none() {
+            bail!("--input needs --day");
+        }
+        if !bench.params.is_empty() && bench.day.is_none() {
+            bail!("--param needs --day");
+        }
+        Ok(bench)
+    }
+}
+
+fn bench(args: BenchArgs) -> Result<()> {
+    let days = match args.day {
+        Some(day) => vec![days::find(day).with_context(|| format!("day {day} is not solved"))?],
+        None => days::DAYS.to_vec(),
+    };
+    let mut timings = Vec::new();
+    for day in days {
+        // A single day may read stdin; a sweep over all days only times the cached inputs.
+        let input = if args.day.is_some() {
+            args.input.read(day.day())?
+        } else {
+            let path = input::input_path(&input::input_dir(), day.day(), args.input.example);
+            let Ok(input) = fs::read_to_string(&path) else {
+                eprintln!(
+                    "skipping day {}: {} does not exist",
+                    day.day(),
+                    path.display()
+                );
+                continue;
+            };
+            input
+        };
+        let parts = day
+            .parts()
+            .iter()
+            .copied()
+            .filter(|part| args.parts.as_ref().is_none_or(|parts| parts.contains(part)))
+            .collect::<Vec<_>>();
+        let params = params(day, args.input.example, &args.params)?;
+        let phases = Phase::all(&parts);
+        timings.extend(bench::bench(day, &input, &phases, args.runs, &params));
+    }
+    match args.format {
+        Format::Text => print!("{}", bench::report(&timings)),
+        Format::Json => timings
+            .iter()
+            .for_each(|timing| println!("{}", timing.json())),
+    }
+    let failures = timings
+        .iter()
+        .filter(|timing| timing.samples.is_err())
+        .count();
+    if failures > 0 {
+        bail!("{failures} phases failed");
+    }
+    Ok(())
+}
+
+struct RenderArgs {
+    day: u8,
+    input: InputArgs,
+    params: Vec<String>,
+    out: PathBuf,
+    image: ImageFormat,
+    scale: usize,
+    every: usize,
+}
+
+impl RenderArgs {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut day = None;
+        let mut input = InputArgs::default();
+        let mut params = Vec::new();
+        let mut out = None;
+        let mut image = ImageFormat::default();
+        let mut scale = 4;
+        let mut every = 1;
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().with_context(|| format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--day" => day = Some(value()?.parse().context("--day must be a number")?),
+                "--input" => input.path = Some(value()?.into()),
+                "--example" => input.example = true,
+                "--param" => params.push(value()?),
+                "--out" => out = Some(PathBuf::from(value()?)),
+                "--image" => image = value()?.parse()?,
+                "--scale" => scale = value()?.parse().context("--scale must be a number")?,
+                "--every" => every = value()?.parse().context("--every must be a number")?,
+                _ => bail!("unexpected argument {arg:?}\n{USAGE}"),
+            }
+        }
+        if scale == 0 || every == 0 {
+            bail!("--scale and --every must be at least 1");
+        }
+        Ok(RenderArgs {
+            day: day.with_context(|| format!("--day is required\n{USAGE}"))?,
+            input,
+            params,
+            out: out.with_context(|| format!("--out is required\n{USAGE}"))?,
+            image,
+            scale,
+            every,
+        })
+    }
+}
+
+fn render(args: RenderArgs) -> Result<()> {
+    let day = days::find(args.day).with_context(|| format!("day {} is not solved", args.day))?;
+    let input = args.input.read(args.day)?;
+    let params = params(day, args.input.example, &args.params)?;
+    let mut recorder = Recorder::new(&args.out, args.image, args.scale, args.every)?;
+    day.render(&input, &params, &mut recorder)?;
+    eprintln!(
+        "wrote {} frames to {}",
+        recorder.saved(),
+        recorder.dir().display()
+    );
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let result = match args.next().as_deref() {
+        Some("run") => RunArgs::parse(args).and_then(run),
+        Some("verify") => VerifyArgs::parse(args).and_then(verify),
+        Some("bench") => BenchArgs::parse(args).and_then(bench),
+        Some("render") => RenderArgs::parse(args).and_then(render),
+        _ => Err(anyhow!(USAGE)),
+    };
+    aoc_common::exit_code(result)
+}
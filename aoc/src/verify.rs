@@ -0,0 +1,234 @@
+use crate::table::table;
+use anyhow::{bail, Context, Result};
+use aoc_common::{input, par::*, Day, Params, Part};
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    time::{Duration, Instant},
+};
+use toml::{Table, Value};
+
+/// Known answers, keyed by day, input name and part, as stored in `answers.toml`:
+///
+/// ```toml
+/// [day01.example]
+/// part1 = 11
+/// part2 = 31
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers(BTreeMap<(u8, String, Part), String>);
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+        Answers::parse(&text).with_context(|| format!("invalid answers in {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut answers = BTreeMap::new();
+        for (day_key, inputs) in text.parse::<Table>()? {
+            let day = day_key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .with_context(|| format!("expected a table named dayNN, got [{day_key}]"))?;
+            let Value::Table(inputs) = inputs else {
+                bail!("[{day_key}] must be a table of inputs");
+            };
+            for (name, parts) in inputs {
+                let Value::Table(parts) = parts else {
+                    bail!("[{day_key}.{name}] must be a table of parts");
+                };
+                for (part_key, answer) in parts {
+                    let part = match part_key.as_str() {
+                        "part1" => Part::One,
+                        "part2" => Part::Two,
+                        _ => bail!("[{day_key}.{name}] has unknown key {part_key}"),
+                    };
+                    let answer = match answer {
+                        Value::String(answer) => answer,
+                        Value::Integer(answer) => answer.to_string(),
+                        _ => bail!("{day_key}.{name}.{part_key} must be a string or an integer"),
+                    };
+                    answers.insert((day, name.clone(), part), answer);
+                }
+            }
+        }
+        Ok(Answers(answers))
+    }
+
+    pub fn get(&self, day: u8, input: &str, part: Part) -> Option<&str> {
+        self.0
+            .get(&(day, input.to_string(), part))
+            .map(String::as_str)
+    }
+
+    fn inputs(&self, day: u8) -> impl Iterator<Item = &str> {
+        self.0
+            .keys()
+            .filter(move |(d, _, _)| *d == day)
+            .map(|(_, input, _)| input.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Mismatch,
+    /// The part ran but no answer is recorded for it.
+    Missing,
+    /// The part returned an error or panicked.
+    Error(String),
+    /// An answer is recorded but the input file is not there.
+    NoInput,
+}
+
+#[derive(Clone, Debug)]
+pub struct Check {
+    pub day: u8,
+    pub input: String,
+    pub part: Part,
+    pub expected: Option<String>,
+    pub answer: Option<String>,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+}
+
+impl Check {
+    /// Only recorded answers can fail; unrecorded parts and absent inputs are reported only.
+    pub fn is_failure(&self) -> bool {
+        match self.outcome {
+            Outcome::Mismatch => true,
+            Outcome::Error(_) => self.expected.is_some(),
+            _ => false,
+        }
+    }
+}
+
+/// Runs every day against every input stored in `dir` or named in `answers`. Inputs named
+/// `example...` run with the example's parameters.
+/// With the `parallel` feature the days run concurrently, so their times include contention.
+pub fn verify(days: &[&dyn Day], answers: &Answers, dir: &Path) -> Result<Vec<Check>> {
+    let checks = days
+        .par_iter()
+        .map(|&day| verify_day(day, answers, dir))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(checks.into_iter().flatten().collect())
+}
+
+fn verify_day(day: &dyn Day, answers: &Answers, dir: &Path) -> Result<Vec<Check>> {
+    let mut checks = Vec::new();
+    let mut inputs = input::stored_inputs(dir, day.day())?;
+    inputs.extend(answers.inputs(day.day()).map(str::to_string));
+    inputs.sort();
+    inputs.dedup();
+    for name in inputs {
+        let path = input::named_input_path(dir, day.day(), &name);
+        let text = path
+            .is_file()
+            .then(|| fs::read_to_string(&path))
+            .transpose()
+            .with_context(|| format!("cannot read {}", path.display()))?;
+        for &part in day.parts() {
+            let expected = answers.get(day.day(), &name, part).map(str::to_string);
+            let check = match &text {
+                Some(text) => run_part(day, text, part, expected, &name),
+                None => Check {
+                    day: day.day(),
+                    input: name.clone(),
+                    part,
+                    expected,
+                    answer: None,
+                    outcome: Outcome::NoInput,
+                    elapsed: Duration::ZERO,
+                },
+            };
+            checks.push(check);
+        }
+    }
+    Ok(checks)
+}
+
+fn run_part(day: &dyn Day, text: &str, part: Part, expected: Option<String>, name: &str) -> Check {
+    let start = Instant::now();
+    let params = Params::new(day.params(), name.starts_with("example"));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| day.run(text, &[part], &params)));
+    let elapsed = start.elapsed();
+    let (answer, outcome) = match result {
+        Ok(Ok(mut answers)) => {
+            let answer = answers.pop().map(|answer| answer.value).unwrap_or_default();
+            let outcome = match &expected {
+                Some(expected) if *expected == answer => Outcome::Pass,
+                Some(_) => Outcome::Mismatch,
+                None => Outcome::Missing,
+            };
+            (Some(answer), outcome)
+        }
+        Ok(Err(err)) => (None, Outcome::Error(format!("{err:#}"))),
+        Err(payload) => (None, Outcome::Error(panic_message(payload))),
+    };
+    Check {
+        day: day.day(),
+        input: name.to_string(),
+        part,
+        expected,
+        answer,
+        outcome,
+        elapsed,
+    }
+}
+
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    format!("panicked: {message}")
+}
+
+/// Lays the checks out as a table, one row per day, input and part, with a summary line.
+pub fn report(checks: &[Check]) -> String {
+    let header = [
+        "day", "input", "part", "expected", "answer", "time", "result",
+    ];
+    let rows = checks
+        .iter()
+        .map(|check| {
+            let result = match &check.outcome {
+                Outcome::Pass => "ok".to_string(),
+                Outcome::Mismatch => "MISMATCH".to_string(),
+                Outcome::Missing => "missing answer".to_string(),
+                Outcome::Error(err) if check.is_failure() => format!("ERROR: {err}"),
+                Outcome::Error(err) => format!("error: {err}"),
+                Outcome::NoInput => "no input".to_string(),
+            };
+            [
+                format!("{:02}", check.day),
+                check.input.clone(),
+                check.part.to_string(),
+                check.expected.clone().unwrap_or_default(),
+                check.answer.clone().unwrap_or_default(),
+                format!("{:.1?}", check.elapsed),
+                result,
+            ]
+        })
+        .collect::<Vec<_>>();
+    let mut out = table(header, &rows);
+    let count = |outcome: fn(&Check) -> bool| checks.iter().filter(|check| outcome(check)).count();
+    writeln!(
+        out,
+        "{} passed, {} failed, {} missing answers, {} without input, {:.1?} in total",
+        count(|check| check.outcome == Outcome::Pass),
+        count(Check::is_failure),
+        count(|check| check.outcome == Outcome::Missing),
+        count(|check| check.outcome == Outcome::NoInput),
+        checks.iter().map(|check| check.elapsed).sum::<Duration>(),
+    )
+    .unwrap();
+    out
+}
@@ -0,0 +1,18 @@
+use aoc::{
+    days::DAYS,
+    verify::{report, verify, Answers},
+};
+use std::path::Path;
+
+#[test]
+fn stored_inputs_give_the_recorded_answers() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let answers = Answers::load(&root.join("answers.toml")).unwrap();
+    let checks = verify(DAYS, &answers, &root.join("inputs")).unwrap();
+    let report = report(&checks);
+    println!("{report}");
+    assert!(
+        checks.iter().all(|check| !check.is_failure()),
+        "some answers do not match:\n{report}"
+    );
+}
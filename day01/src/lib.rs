@@ -0,0 +1,74 @@
+use anyhow::Result;
+use aoc_common::{parse, ParseError, Solution};
+use std::collections::HashMap;
+
+fn part1(input: &[(i32, i32)]) -> u32 {
+    let (mut vec1, mut vec2): (Vec<i32>, Vec<i32>) = input.iter().cloned().unzip();
+    vec1.sort();
+    vec2.sort();
+    vec1.into_iter()
+        .zip(vec2)
+        .map(|(id1, id2)| id1.abs_diff(id2))
+        .sum()
+}
+
+fn part2(input: &[(i32, i32)]) -> i32 {
+    let freq = input
+        .iter()
+        .map(|(_, id2)| id2)
+        .fold(HashMap::new(), |freq, id| {
+            let mut freq = freq;
+            *freq.entry(id).or_insert(0) += 1;
+            freq
+        });
+    input
+        .iter()
+        .map(|(id1, _)| id1)
+        .map(|id| id * freq.get(id).unwrap_or(&0))
+        .sum()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Input = Vec<(i32, i32)>;
+    type Answer1 = u32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input)
+            .map(|line| {
+                let (id1, id2) = line.split_once(line.text, "   ")?;
+                Ok((line.number(id1)?, line.number(id2)?))
+            })
+            .collect::<Result<_, ParseError>>()?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u32> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<i32> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day01.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day01::parse(EXAMPLE).unwrap();
+        assert_eq!(Day01::part1(&input).unwrap(), 11);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day01::parse(EXAMPLE).unwrap();
+        assert_eq!(Day01::part2(&input).unwrap(), 31);
+    }
+}
@@ -0,0 +1,112 @@
+use anyhow::Result;
+use aoc_common::{parse, ParseError, Solution};
+
+fn is_safe(report: &[i32]) -> bool {
+    // Dropping a level from a report of two leaves one, which nothing can make unsafe.
+    let [first, second, ..] = *report else {
+        return true;
+    };
+    let sign = (first - second).signum();
+    (0..report.len() - 1)
+        .map(|i| (report[i], report[i + 1]))
+        .map(|(a, b)| a - b)
+        .all(|d| d.signum() == sign && d.abs() <= 3 && d.abs() >= 1)
+}
+
+fn is_almost_safe(report: &[i32]) -> bool {
+    is_safe(report)
+        || (0..report.len())
+            .map(|i| {
+                let it = report.iter();
+                it.clone()
+                    .take(i)
+                    .chain(it.clone().skip(i + 1))
+                    .copied()
+                    .collect::<Vec<_>>()
+            })
+            .any(|new_report| is_safe(&new_report[..]))
+}
+
+fn part1(input: &[Vec<i32>]) -> usize {
+    input.iter().filter(|report| is_safe(report)).count()
+}
+
+fn part2(input: &[Vec<i32>]) -> usize {
+    input.iter().filter(|report| is_almost_safe(report)).count()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input = Vec<Vec<i32>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input)
+            .map(|line| {
+                let report = line
+                    .text
+                    .split_whitespace()
+                    .map(|level| line.number(level))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if report.len() < 2 {
+                    return Err(line.end_error("another level"));
+                }
+                Ok(report)
+            })
+            .collect::<Result<_, ParseError>>()?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day02.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day02::parse(EXAMPLE).unwrap();
+        assert_eq!(Day02::part1(&input).unwrap(), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day02::parse(EXAMPLE).unwrap();
+        assert_eq!(Day02::part2(&input).unwrap(), 4);
+    }
+
+    #[test]
+    fn malformed_level_is_reported() {
+        let err = Day02::parse("7 6 4\n1 x 3\n").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 3, "x"));
+    }
+
+    #[test]
+    fn single_level_is_reported() {
+        let err = Day02::parse("7 6 4\n1\n").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 2, "another level")
+        );
+    }
+
+    #[test]
+    fn two_levels_can_drop_one() {
+        let input = Day02::parse("1 1\n1 5\n1 2\n").unwrap();
+        assert_eq!(part1(&input), 1);
+        assert_eq!(part2(&input), 3);
+    }
+}
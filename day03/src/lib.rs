@@ -0,0 +1,85 @@
+use anyhow::Result;
+use aoc_common::Solution;
+use regex::Regex;
+
+fn part1(input: &str) -> Result<i32> {
+    let re = Regex::new(r"mul\(([0-9]+),([0-9]+)\)")?;
+    let result = re
+        .captures_iter(input)
+        .map(|c| c.extract().1)
+        .map(|[a, b]| (a.parse::<i32>().unwrap(), b.parse::<i32>().unwrap()))
+        .map(|(a, b)| a * b)
+        .sum();
+    Ok(result)
+}
+
+enum Cmd {
+    Do,
+    Dont,
+    Mul(i32),
+}
+
+fn part2(input: &str) -> Result<i32> {
+    let re = Regex::new(r"mul\([0-9]+,[0-9]+\)|do\(\)|don't\(\)")?;
+    let result = re
+        .find_iter(input)
+        .map(|m| m.as_str())
+        .filter_map(|m| {
+            if m == "do()" {
+                return Some(Cmd::Do);
+            }
+            if m == "don't()" {
+                return Some(Cmd::Dont);
+            }
+            assert!(m.starts_with("mul("));
+            let (a, b) = m.strip_prefix("mul(")?.strip_suffix(")")?.split_once(",")?;
+            Some(Cmd::Mul(a.parse::<i32>().ok()? * b.parse::<i32>().ok()?))
+        })
+        .fold((0, true), |(sum, enabled), cmd| match cmd {
+            Cmd::Do => (sum, true),
+            Cmd::Dont => (sum, false),
+            Cmd::Mul(n) => (if enabled { sum + n } else { sum }, enabled),
+        })
+        .0;
+    Ok(result)
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Input = String;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &String) -> Result<i32> {
+        part1(input)
+    }
+
+    fn part2(input: &String) -> Result<i32> {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day03.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day03::parse(EXAMPLE).unwrap();
+        assert_eq!(Day03::part1(&input).unwrap(), 161);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day03::parse(EXAMPLE).unwrap();
+        assert_eq!(Day03::part2(&input).unwrap(), 48);
+    }
+}
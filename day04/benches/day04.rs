@@ -0,0 +1,8 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn bench(c: &mut Criterion) {
+    aoc_common::bench::criterion::<day04::Day04>(c);
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use aoc_common::{Dir, Grid, Solution};
+use std::iter;
+
+fn part1(grid: &Grid<char>) -> usize {
+    grid.positions()
+        .map(|pos| {
+            Dir::ALL
+                .into_iter()
+                .map(|dir| {
+                    iter::successors(Some(pos), |&pos| Some(pos + dir))
+                        .take(4)
+                        .filter_map(|pos| grid.get(pos))
+                        .collect::<String>()
+                })
+                .filter(|str| str == "XMAS")
+                .count()
+        })
+        .sum()
+}
+
+fn part2(grid: &Grid<char>) -> usize {
+    grid.positions()
+        .filter(|&pos| {
+            let word1 = [pos + Dir::UpLeft, pos, pos + Dir::DownRight]
+                .into_iter()
+                .filter_map(|pos| grid.get(pos))
+                .collect::<String>();
+            let word2 = [pos + Dir::UpRight, pos, pos + Dir::DownLeft]
+                .into_iter()
+                .filter_map(|pos| grid.get(pos))
+                .collect::<String>();
+            (word1 == "MAS" || word1 == "SAM") && (word2 == "MAS" || word2 == "SAM")
+        })
+        .count()
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<char>> {
+        Ok(Grid::parse(input)?)
+    }
+
+    fn part1(grid: &Grid<char>) -> Result<usize> {
+        Ok(part1(grid))
+    }
+
+    fn part2(grid: &Grid<char>) -> Result<usize> {
+        Ok(part2(grid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day04.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day04::parse(EXAMPLE).unwrap();
+        assert_eq!(Day04::part1(&input).unwrap(), 18);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day04::parse(EXAMPLE).unwrap();
+        assert_eq!(Day04::part2(&input).unwrap(), 9);
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::Result;
+use aoc_common::{parse, ParseError, Solution};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+fn part1(ord_rules: &[(i32, i32)], updates: &[Vec<i32>]) -> i32 {
+    let mut updates = updates
+        .iter()
+        .map(|update| {
+            (
+                update[update.len() / 2],
+                update
+                    .iter()
+                    .enumerate()
+                    .fold(HashMap::new(), |mut pages, (idx, page)| {
+                        pages.insert(page, idx);
+                        pages
+                    }),
+                true,
+            )
+        })
+        .collect::<Vec<_>>();
+    for (a, b) in ord_rules.iter() {
+        for (_, pages, valid) in updates.iter_mut() {
+            if let (Some(a_idx), Some(b_idx)) = (pages.get(a), pages.get(b)) {
+                if a_idx > b_idx {
+                    *valid = false;
+                }
+            }
+        }
+    }
+    updates
+        .iter()
+        .filter(|(_, _, valid)| *valid)
+        .map(|(mid, _, _)| mid)
+        .sum()
+}
+
+fn part2(ord_rules: &[(i32, i32)], updates: &[Vec<i32>]) -> i32 {
+    let mut updates = updates
+        .iter()
+        .map(|update| {
+            (
+                update.clone(),
+                update
+                    .iter()
+                    .enumerate()
+                    .fold(HashMap::new(), |mut pages, (idx, page)| {
+                        pages.insert(page, idx);
+                        pages
+                    }),
+                true,
+            )
+        })
+        .collect::<Vec<_>>();
+    for (a, b) in ord_rules.iter() {
+        for (_, pages, valid) in updates.iter_mut() {
+            if let (Some(a_idx), Some(b_idx)) = (pages.get(a), pages.get(b)) {
+                if a_idx > b_idx {
+                    *valid = false;
+                }
+            }
+        }
+    }
+    let ord_rules = ord_rules
+        .iter()
+        .fold(HashMap::new(), |mut ord_rules, (a, b)| {
+            ord_rules.entry(a).or_insert(HashSet::new()).insert(b);
+            ord_rules
+        });
+    updates
+        .into_iter()
+        .filter(|(_, _, valid)| !*valid)
+        .map(|(mut pages, _, _)| {
+            pages.sort_by(|a, b| {
+                if ord_rules.get(&a).is_some_and(|set| set.contains(b)) {
+                    return Ordering::Less;
+                }
+                if ord_rules.get(&b).is_some_and(|set| set.contains(a)) {
+                    return Ordering::Greater;
+                }
+                Ordering::Equal
+            });
+            pages
+        })
+        .map(|sorted_pages| sorted_pages[sorted_pages.len() / 2])
+        .sum()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Input = (Vec<(i32, i32)>, Vec<Vec<i32>>);
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = parse::lines(input);
+        let ord_rules = lines
+            .block()
+            .map(|line| {
+                let (a, b) = line.split_once(line.text, "|")?;
+                Ok((line.number(a)?, line.number(b)?))
+            })
+            .collect::<Result<_, ParseError>>()?;
+        let updates = lines
+            .map(|line| line.text.split(",").map(|page| line.number(page)).collect())
+            .collect::<Result<_, ParseError>>()?;
+        Ok((ord_rules, updates))
+    }
+
+    fn part1((ord_rules, updates): &Self::Input) -> Result<i32> {
+        Ok(part1(ord_rules, updates))
+    }
+
+    fn part2((ord_rules, updates): &Self::Input) -> Result<i32> {
+        Ok(part2(ord_rules, updates))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day05.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(Day05::part1(&input).unwrap(), 143);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(Day05::part2(&input).unwrap(), 123);
+    }
+}
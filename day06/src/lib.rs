@@ -0,0 +1,139 @@
+use anyhow::{Context, Result};
+use aoc_common::{
+    par::*,
+    render::{Frame, Recorder, BLACK, BLUE, GREY, YELLOW},
+    Dir, Grid, Params, Pos, Solution,
+};
+use std::collections::HashSet;
+
+fn part1(grid: &Grid<char>) -> Option<usize> {
+    let mut pos = grid.find(&'^')?;
+    let mut dir = Dir::Up;
+    let mut positions = HashSet::new();
+    loop {
+        positions.insert(pos);
+        while let Some(&'#') = grid.get(pos + dir) {
+            dir = dir.turn_right();
+        }
+        if grid.get(pos + dir).is_none() {
+            break;
+        }
+        pos += dir;
+    }
+    Some(positions.len())
+}
+
+fn obstacle_loops(grid: &Grid<char>, obstacle: Pos, dir: Dir) -> bool {
+    if grid.get(obstacle) != Some(&'.') {
+        return false;
+    }
+    let blocked = |pos: Pos| pos == obstacle || grid.get(pos) == Some(&'#');
+    let mut pos = obstacle - dir;
+    let mut dir = dir;
+    let mut positions = HashSet::new();
+    loop {
+        if !positions.insert((pos, dir)) {
+            return true;
+        }
+        while blocked(pos + dir) {
+            dir = dir.turn_right();
+        }
+        if grid.get(pos + dir).is_none() {
+            return false;
+        }
+        pos += dir;
+    }
+}
+
+fn part2(grid: &Grid<char>) -> Option<usize> {
+    let mut pos = grid.find(&'^')?;
+    let mut dir = Dir::Up;
+    let mut visited = HashSet::new();
+    // Each position is tested once, facing the way the guard first walks into it: an obstacle
+    // on a position already walked through would have changed the path before this point.
+    let mut candidates = Vec::new();
+    loop {
+        visited.insert(pos);
+        while let Some(&'#') = grid.get(pos + dir) {
+            dir = dir.turn_right();
+        }
+        if grid.get(pos + dir).is_none() {
+            break;
+        }
+        pos += dir;
+        if !visited.contains(&pos) {
+            candidates.push((pos, dir));
+        }
+    }
+    Some(
+        candidates
+            .into_par_iter()
+            .filter(|&(pos, dir)| obstacle_loops(grid, pos, dir))
+            .count(),
+    )
+}
+
+/// A frame per step of the guard's walk, with the cells already visited in blue.
+fn render(grid: &Grid<char>, recorder: &mut Recorder) -> Result<()> {
+    let mut pos = grid.find(&'^').context("the map has no guard")?;
+    let mut dir = Dir::Up;
+    let mut frame = Frame::from_grid(grid, |&ch| if ch == '#' { GREY } else { BLACK });
+    loop {
+        frame.paint(pos, YELLOW);
+        recorder.record(|| frame.clone())?;
+        while let Some(&'#') = grid.get(pos + dir) {
+            dir = dir.turn_right();
+        }
+        if grid.get(pos + dir).is_none() {
+            return recorder.finish(|| frame);
+        }
+        frame.paint(pos, BLUE);
+        pos += dir;
+    }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Input = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<char>> {
+        Ok(Grid::parse_with(input, "'.', '#' or '^'", |ch| {
+            matches!(ch, '.' | '#' | '^').then_some(ch)
+        })?)
+    }
+
+    fn part1(grid: &Grid<char>) -> Result<usize> {
+        part1(grid).context("the map has no guard")
+    }
+
+    fn part2(grid: &Grid<char>) -> Result<usize> {
+        part2(grid).context("the map has no guard")
+    }
+
+    fn render(grid: &Grid<char>, _: &Params, recorder: &mut Recorder) -> Result<()> {
+        render(grid, recorder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day06.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day06::parse(EXAMPLE).unwrap();
+        assert_eq!(Day06::part1(&input).unwrap(), 41);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day06::parse(EXAMPLE).unwrap();
+        assert_eq!(Day06::part2(&input).unwrap(), 6);
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::Result;
+use aoc_common::{par::*, parse, ParseError, Solution};
+
+type Input = Vec<(i64, Vec<i64>)>;
+
+fn is_valid(target: i64, init: i64, operands: &[i64]) -> bool {
+    if operands.is_empty() {
+        return false;
+    }
+    match operands {
+        [] => false,
+        [first, rest @ ..] => {
+            init * first == target
+                || init + first == target
+                || init * first < target && is_valid(target, init * first, rest)
+                || init + first < target && is_valid(target, init + first, rest)
+        }
+    }
+}
+
+fn part1(input: &Input) -> i64 {
+    input
+        .par_iter()
+        .filter(|&(test_value, operands)| is_valid(*test_value, 0, operands))
+        .map(|&(test_value, _)| test_value)
+        .sum()
+}
+
+fn is_valid2(target: i64, init: Option<i64>, operands: &[i64]) -> bool {
+    if operands.is_empty() {
+        return false;
+    }
+    match operands {
+        [] => false,
+        [first, rest @ ..] => {
+            let mul = init.unwrap_or(1) * first;
+            let add = init.unwrap_or(0) + first;
+            let cat = match init {
+                None => *first,
+                Some(num) => format!("{num}{first}").parse().unwrap(),
+            };
+            (mul == target || cat == target || add == target) && rest.is_empty()
+                || mul <= target && is_valid2(target, Some(mul), rest)
+                || cat <= target && is_valid2(target, Some(cat), rest)
+                || add <= target && is_valid2(target, Some(add), rest)
+        }
+    }
+}
+
+fn part2(input: &Input) -> i64 {
+    input
+        .par_iter()
+        .filter(|&(test_value, operands)| is_valid2(*test_value, None, operands))
+        .map(|&(test_value, _)| test_value)
+        .sum()
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Input = Input;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(parse::lines(input)
+            .map(|line| {
+                let (test_value, operands) = line.split_once(line.text, ": ")?;
+                let operands = operands
+                    .split(" ")
+                    .map(|operand| line.number(operand))
+                    .collect::<Result<_, _>>()?;
+                Ok((line.number(test_value)?, operands))
+            })
+            .collect::<Result<_, ParseError>>()?)
+    }
+
+    fn part1(input: &Input) -> Result<i64> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Input) -> Result<i64> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day07.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day07::parse(EXAMPLE).unwrap();
+        assert_eq!(Day07::part1(&input).unwrap(), 3749);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day07::parse(EXAMPLE).unwrap();
+        assert_eq!(Day07::part2(&input).unwrap(), 11387);
+    }
+}
@@ -0,0 +1,109 @@
+use anyhow::Result;
+use aoc_common::{Grid, Pos, Solution};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+fn part1(grid: &Grid<char>) -> usize {
+    grid.iter()
+        .filter_map(|(pos, &ch)| match ch {
+            '.' => None,
+            ch => Some((ch, pos)),
+        })
+        .fold(HashMap::new(), |mut groups, (freq, pos)| {
+            groups.entry(freq).or_insert(Vec::new()).push(pos);
+            groups
+        })
+        .into_values()
+        .flat_map(|positions| {
+            positions
+                .iter()
+                .cartesian_product(positions.iter())
+                .filter(|(p1, p2)| p1 != p2)
+                .flat_map(|(&p1, &p2)| [p1 + p1 - p2, p2 + p2 - p1].into_iter())
+                .collect::<Vec<_>>()
+        })
+        .filter(|&pos| grid.contains(pos))
+        .fold(HashSet::new(), |mut antinodes, pos| {
+            antinodes.insert(pos);
+            antinodes
+        })
+        .len()
+}
+
+fn part2(grid: &Grid<char>) -> usize {
+    grid.iter()
+        .filter_map(|(pos, &ch)| match ch {
+            '.' => None,
+            ch => Some((ch, pos)),
+        })
+        .fold(HashMap::new(), |mut groups, (freq, pos)| {
+            groups.entry(freq).or_insert(Vec::new()).push(pos);
+            groups
+        })
+        .into_values()
+        .flat_map(|positions| {
+            let within_bounds = |&p: &Pos| grid.contains(p);
+            positions
+                .iter()
+                .cartesian_product(positions.iter())
+                .filter(|(p1, p2)| p1 != p2)
+                .flat_map(|(p1, p2)| {
+                    (0..)
+                        .map(|n| *p1 + (*p1 - *p2) * n)
+                        .take_while(within_bounds)
+                        .chain(
+                            (0..)
+                                .map(|n| *p2 + (*p2 - *p1) * n)
+                                .take_while(within_bounds),
+                        )
+                })
+                .collect::<Vec<_>>()
+        })
+        .fold(HashSet::new(), |mut antinodes, pos| {
+            antinodes.insert(pos);
+            antinodes
+        })
+        .len()
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Input = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<char>> {
+        Ok(Grid::parse_with(input, "'.' or an antenna", |ch| {
+            (ch == '.' || ch.is_ascii_alphanumeric()).then_some(ch)
+        })?)
+    }
+
+    fn part1(grid: &Grid<char>) -> Result<usize> {
+        Ok(part1(grid))
+    }
+
+    fn part2(grid: &Grid<char>) -> Result<usize> {
+        Ok(part2(grid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day08.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day08::parse(EXAMPLE).unwrap();
+        assert_eq!(Day08::part1(&input).unwrap(), 14);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day08::parse(EXAMPLE).unwrap();
+        assert_eq!(Day08::part2(&input).unwrap(), 34);
+    }
+}
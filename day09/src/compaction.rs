@@ -0,0 +1,75 @@
+//! Ways of moving files towards the start of the disk, each built on [`Memory::alloc`] and
+//! [`Memory::free`] so that the results compare by the same [`Memory::checksum`].
+
+use crate::{FileId, Memory};
+
+/// Decides where one file goes when the disk is compacted.
+pub trait CompactionPolicy {
+    /// Moves some or all of the `size` blocks of `file`, which start at `addr`, into free
+    /// chunks before it, or leaves it where it is.
+    fn place(&self, memory: &mut Memory, addr: u64, size: u64, file: FileId);
+}
+
+/// Moves a file one block at a time from its end into the lowest free block, splitting it
+/// across as many chunks as it takes. This is the first half of the puzzle.
+pub struct BlockFragmenting;
+
+impl CompactionPolicy for BlockFragmenting {
+    fn place(&self, memory: &mut Memory, addr: u64, mut size: u64, file: FileId) {
+        while size > 0 {
+            let Some(free_addr) = memory.free_chunks(1, addr).into_iter().min() else {
+                break;
+            };
+            let moved = memory.alloc(free_addr, size, file);
+            memory.free(addr, moved);
+            size -= moved;
+        }
+    }
+}
+
+/// Moves a whole file to the lowest free chunk it fits in. This is the second half of the
+/// puzzle.
+pub struct FirstFit;
+
+impl CompactionPolicy for FirstFit {
+    fn place(&self, memory: &mut Memory, addr: u64, size: u64, file: FileId) {
+        if let Some(free_addr) = memory.free_chunks(size, addr).into_iter().min() {
+            move_whole(memory, free_addr, addr, size, file);
+        }
+    }
+}
+
+/// Moves a whole file to the lowest of the smallest free chunks it fits in, leaving the
+/// biggest chunks for the files after it. Chunks of [`crate::LARGEST_CLASS`] blocks or more
+/// count as one size.
+pub struct BestFit;
+
+impl CompactionPolicy for BestFit {
+    fn place(&self, memory: &mut Memory, addr: u64, size: u64, file: FileId) {
+        if let Some(&free_addr) = memory.free_chunks(size, addr).first() {
+            move_whole(memory, free_addr, addr, size, file);
+        }
+    }
+}
+
+/// Moves a whole file to the lowest of the largest free chunks, so that what it leaves is big
+/// enough for another file. Chunks of [`crate::LARGEST_CLASS`] blocks or more count as one
+/// size.
+pub struct WorstFit;
+
+impl CompactionPolicy for WorstFit {
+    fn place(&self, memory: &mut Memory, addr: u64, size: u64, file: FileId) {
+        if let Some(&free_addr) = memory.free_chunks(size, addr).last() {
+            move_whole(memory, free_addr, addr, size, file);
+        }
+    }
+}
+
+/// Moves the file into the chunk at `free_addr`, if the chunk holds all of it.
+fn move_whole(memory: &mut Memory, free_addr: u64, addr: u64, size: u64, file: FileId) {
+    if !memory.fits(free_addr, size) {
+        return;
+    }
+    let moved = memory.alloc(free_addr, size, file);
+    memory.free(addr, moved);
+}
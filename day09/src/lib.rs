@@ -0,0 +1,506 @@
+pub mod compaction;
+
+use anyhow::Result;
+use aoc_common::{parse, Solution};
+use compaction::{BlockFragmenting, CompactionPolicy, FirstFit};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap},
+    fmt::{self, Display},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileId(pub u64);
+
+#[derive(Clone, Copy, Debug)]
+enum Chunk {
+    Free(u64),
+    Used(u64, FileId),
+}
+
+impl Chunk {
+    fn fits(self, size: u64) -> bool {
+        matches!(self, Chunk::Free(free_size) if free_size >= size)
+    }
+}
+
+/// Free chunks of this many blocks or more share the last size class, since any file a disk map
+/// describes fits them.
+pub const LARGEST_CLASS: u64 = 9;
+
+/// The disk as chunks of blocks, each free or holding part of a file, by address.
+#[derive(Debug)]
+pub struct Memory {
+    chunks: BTreeMap<u64, Chunk>,
+    /// The addresses of free chunks by size class, lowest first. Entries go stale as chunks are
+    /// used or merged, and are dropped when they reach the top.
+    free: [BinaryHeap<Reverse<u64>>; LARGEST_CLASS as usize + 1],
+    /// Files the last compaction left where they were.
+    unmoved: usize,
+}
+
+impl From<Vec<u64>> for Memory {
+    fn from(input: Vec<u64>) -> Memory {
+        let mut memory = Memory {
+            chunks: BTreeMap::new(),
+            free: Default::default(),
+            unmoved: 0,
+        };
+        let mut addr = 0;
+        for (idx, size) in input.into_iter().enumerate() {
+            match idx % 2 {
+                0 if size != 0 => {
+                    memory
+                        .chunks
+                        .insert(addr, Chunk::Used(size, FileId(idx as u64 / 2)));
+                }
+                // An empty file leaves the free chunks either side of it touching.
+                1 if size != 0 => match memory.chunks.last_key_value() {
+                    Some((&prev, &Chunk::Free(prev_size))) if prev + prev_size == addr => {
+                        memory.insert_free(prev, prev_size + size)
+                    }
+                    _ => memory.insert_free(addr, size),
+                },
+                _ => {}
+            }
+            addr += size;
+        }
+        memory
+    }
+}
+
+impl Memory {
+    fn insert_free(&mut self, addr: u64, size: u64) {
+        if size == 0 {
+            return;
+        }
+        self.chunks.insert(addr, Chunk::Free(size));
+        self.free[size.min(LARGEST_CLASS) as usize].push(Reverse(addr));
+    }
+
+    /// For each size class that fits `size` blocks, smallest first, the address of its lowest
+    /// free chunk, if that is below `before`. For a file bigger than [`LARGEST_CLASS`], which no
+    /// disk map digit makes, the last class falls back to scanning for a chunk big enough.
+    pub fn free_chunks(&mut self, size: u64, before: u64) -> Vec<u64> {
+        let mut lowest = Vec::new();
+        for class in size.min(LARGEST_CLASS)..=LARGEST_CLASS {
+            let heap = &mut self.free[class as usize];
+            while let Some(&Reverse(addr)) = heap.peek() {
+                match self.chunks.get(&addr) {
+                    Some(&Chunk::Free(free_size)) if free_size.min(LARGEST_CLASS) == class => {
+                        break;
+                    }
+                    _ => {
+                        heap.pop();
+                    }
+                }
+            }
+            match heap.peek() {
+                Some(&Reverse(addr)) if addr < before && self.chunks[&addr].fits(size) => {
+                    lowest.push(addr)
+                }
+                Some(&Reverse(addr)) if addr < before => lowest.extend(
+                    self.chunks
+                        .range(addr..before)
+                        .find(|(_, chunk)| chunk.fits(size))
+                        .map(|(&addr, _)| addr),
+                ),
+                _ => {}
+            }
+        }
+        lowest
+    }
+
+    /// Whether the chunk at `addr` is free and at least `size` blocks long.
+    pub fn fits(&self, addr: u64, size: u64) -> bool {
+        self.chunks.get(&addr).is_some_and(|chunk| chunk.fits(size))
+    }
+
+    /// Puts up to `size` blocks of `file` at the start of the free chunk at `addr`, returning how
+    /// many fit.
+    pub fn alloc(&mut self, addr: u64, size: u64, file: FileId) -> u64 {
+        if let Some(&Chunk::Free(chunk_size)) = self.chunks.get(&addr) {
+            let size = size.min(chunk_size);
+            self.chunks.insert(addr, Chunk::Used(size, file));
+            self.insert_free(addr + size, chunk_size - size);
+            return size;
+        }
+        0
+    }
+
+    /// Frees the last `size` blocks of the file at `addr`, merging them with any free chunks
+    /// either side.
+    pub fn free(&mut self, addr: u64, size: u64) {
+        let Some(&Chunk::Used(used_size, file)) = self.chunks.get(&addr) else {
+            return;
+        };
+        let size = size.min(used_size);
+        let mut start = addr + used_size - size;
+        let mut end = addr + used_size;
+        if size < used_size {
+            self.chunks
+                .insert(addr, Chunk::Used(used_size - size, file));
+        } else {
+            self.chunks.remove(&addr);
+            if let Some((&prev_addr, &Chunk::Free(_))) = self.chunks.range(..addr).next_back() {
+                start = prev_addr;
+            }
+        }
+        if let Some(&Chunk::Free(next_size)) = self.chunks.get(&end) {
+            self.chunks.remove(&end);
+            end += next_size;
+        }
+        self.insert_free(start, end - start);
+    }
+
+    /// Moves every file as `policy` decides, highest ID first. A file already split by an
+    /// earlier compaction has each of its chunks placed in turn, the last one first.
+    pub fn compact(&mut self, policy: &dyn CompactionPolicy) {
+        let mut files = self
+            .chunks
+            .iter()
+            .filter_map(|(&addr, &chunk)| match chunk {
+                Chunk::Used(size, FileId(id)) => Some((id, addr, size)),
+                Chunk::Free(_) => None,
+            })
+            .collect::<Vec<_>>();
+        files.sort_unstable_by(|a, b| b.cmp(a));
+        let mut moved = BTreeMap::new();
+        for (id, addr, size) in files {
+            policy.place(self, addr, size, FileId(id));
+            let stayed =
+                matches!(self.chunks.get(&addr), Some(&Chunk::Used(left, _)) if left == size);
+            *moved.entry(id).or_insert(false) |= !stayed;
+        }
+        self.unmoved = moved.values().filter(|&&moved| !moved).count();
+    }
+
+    /// The layout as a disk map, numbering the files in the order they lie. A chunk longer than
+    /// 9 blocks, or two chunks of the same kind in a row, get a 0 between their digits, so a
+    /// map with no empty files that does not end in a 0 comes back from [`Memory::from`]
+    /// unchanged.
+    pub fn disk_map(&self) -> Vec<u64> {
+        let mut disk_map = Vec::new();
+        for &chunk in self.chunks.values() {
+            let (mut size, is_file) = match chunk {
+                Chunk::Free(size) => (size, false),
+                Chunk::Used(size, _) => (size, true),
+            };
+            loop {
+                if disk_map.len().is_multiple_of(2) != is_file {
+                    disk_map.push(0);
+                }
+                let digit = size.min(9);
+                disk_map.push(digit);
+                size -= digit;
+                if size == 0 {
+                    break;
+                }
+            }
+        }
+        disk_map
+    }
+
+    pub fn fragmentation(&self) -> Fragmentation {
+        let end = self
+            .chunks
+            .iter()
+            .rfind(|(_, chunk)| matches!(chunk, Chunk::Used(..)))
+            .map_or(0, |(&addr, _)| addr);
+        let free_spans = self
+            .chunks
+            .range(..end)
+            .filter_map(|(_, &chunk)| match chunk {
+                Chunk::Free(size) => Some(size),
+                Chunk::Used(..) => None,
+            })
+            .collect::<Vec<_>>();
+        Fragmentation {
+            free_spans: free_spans.len(),
+            largest_free_span: free_spans.into_iter().max().unwrap_or(0),
+            unmoved_files: self.unmoved,
+        }
+    }
+
+    pub fn checksum(&self) -> u64 {
+        self.chunks
+            .iter()
+            .filter_map(|(&addr, chunk)| {
+                if let &Chunk::Used(size, FileId(fid)) = chunk {
+                    Some(
+                        (addr..(addr + size))
+                            .map(|offset| offset * fid)
+                            .sum::<u64>(),
+                    )
+                } else {
+                    None
+                }
+            })
+            .sum()
+    }
+}
+
+/// The blocks as the puzzle draws them, `.` for free and otherwise the last digit of the ID of
+/// the file there.
+impl Display for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &chunk in self.chunks.values() {
+            let (size, block) = match chunk {
+                Chunk::Free(size) => (size, '.'),
+                Chunk::Used(size, FileId(id)) => {
+                    (size, char::from_digit((id % 10) as u32, 10).unwrap())
+                }
+            };
+            for _ in 0..size {
+                write!(f, "{block}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How scattered the free space between files is, and how much compacting helped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fragmentation {
+    /// Free chunks before the last file.
+    pub free_spans: usize,
+    pub largest_free_span: u64,
+    /// Files the last compaction left where they were.
+    pub unmoved_files: usize,
+}
+
+impl Display for Fragmentation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} free spans, the largest {} blocks, {} files unmoved",
+            self.free_spans, self.largest_free_span, self.unmoved_files
+        )
+    }
+}
+
+fn part1(mut memory: Memory) -> u64 {
+    memory.compact(&BlockFragmenting);
+    memory.checksum()
+}
+
+fn part2(mut memory: Memory) -> u64 {
+    memory.compact(&FirstFit);
+    memory.checksum()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Input = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<u64>> {
+        let mut lines = parse::lines(input);
+        let line = lines.expect("the disk map")?;
+        let disk_map = line
+            .text
+            .char_indices()
+            .map(|(offset, ch)| {
+                ch.to_digit(10).map(u64::from).ok_or_else(|| {
+                    line.error(&line.text[offset..offset + ch.len_utf8()], "a digit")
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        if let Some(line) = lines.next() {
+            return Err(line.error(line.text, "the end of the input").into());
+        }
+        Ok(disk_map)
+    }
+
+    fn part1(input: &Vec<u64>) -> Result<u64> {
+        Ok(part1(Memory::from(input.clone())))
+    }
+
+    fn part2(input: &Vec<u64>) -> Result<u64> {
+        Ok(part2(Memory::from(input.clone())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use compaction::{BestFit, WorstFit};
+
+    const EXAMPLE: &str = include_str!("../../inputs/day09.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day09::parse(EXAMPLE).unwrap();
+        assert_eq!(Day09::part1(&input).unwrap(), 1928);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day09::parse(EXAMPLE).unwrap();
+        assert_eq!(Day09::part2(&input).unwrap(), 2858);
+    }
+
+    /// Moves whole files on a block-by-block picture of the disk, as the puzzle describes it.
+    fn compact_blocks(disk_map: &[u64]) -> u64 {
+        let mut blocks = Vec::new();
+        for (idx, &size) in disk_map.iter().enumerate() {
+            let block = (idx % 2 == 0).then_some(idx as u64 / 2);
+            blocks.extend((0..size).map(|_| block));
+        }
+        for file in (0..disk_map.len().div_ceil(2) as u64).rev() {
+            let Some(start) = blocks.iter().position(|&block| block == Some(file)) else {
+                continue;
+            };
+            let size = blocks[start..]
+                .iter()
+                .take_while(|&&block| block == Some(file))
+                .count();
+            let free = blocks[..start]
+                .windows(size)
+                .position(|window| window.iter().all(Option::is_none));
+            if let Some(free) = free {
+                blocks.copy_within(start..start + size, free);
+                blocks[start..start + size].fill(None);
+            }
+        }
+        blocks
+            .iter()
+            .enumerate()
+            .map(|(addr, block)| addr as u64 * block.unwrap_or(0))
+            .sum()
+    }
+
+    /// A long disk map, with some files of no blocks if `empty_files`.
+    fn generated_disk_map(empty_files: bool) -> Vec<u64> {
+        let mut seed = 9u64;
+        (0..3001)
+            .map(|idx| {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                let digit = seed >> 60 & 7;
+                if idx % 2 == 0 && !empty_files {
+                    digit + 1
+                } else {
+                    digit
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn part2_matches_block_moves() {
+        let empty_file_between_gaps = vec![
+            4, 2, 8, 9, 2, 2, 0, 1, 6, 3, 7, 7, 0, 9, 0, 2, 3, 7, 6, 9, 7, 8, 4, 9, 9, 0, 6, 6, 0,
+            3, 6, 6, 0, 8, 5, 8, 8, 6, 1, 6, 8, 1, 1, 2, 3, 6, 1, 7, 5, 5,
+        ];
+        assert_eq!(part2(Memory::from(empty_file_between_gaps)), 69597);
+        let disk_map = generated_disk_map(true);
+        assert_eq!(
+            part2(Memory::from(disk_map.clone())),
+            compact_blocks(&disk_map)
+        );
+    }
+
+    #[test]
+    fn files_wider_than_a_digit() {
+        for (disk_map, blocks) in [
+            (vec![1, 9, 12], "0.........111111111111"),
+            (vec![1, 13, 12], "0111111111111............."),
+        ] {
+            for policy in [&FirstFit as &dyn CompactionPolicy, &BestFit, &WorstFit] {
+                let mut memory = Memory::from(disk_map.clone());
+                memory.compact(policy);
+                assert_eq!(memory.to_string(), blocks);
+            }
+        }
+    }
+
+    #[test]
+    fn policies_only_move_files_left() {
+        let disk_map = generated_disk_map(true);
+        let files = |memory: &Memory| {
+            let mut files = BTreeMap::<u64, (u64, u64)>::new();
+            for (&addr, &chunk) in &memory.chunks {
+                if let Chunk::Used(size, FileId(file)) = chunk {
+                    let (first, total) = files.entry(file).or_insert((addr, 0));
+                    *first = addr.min(*first);
+                    *total += size;
+                }
+            }
+            files
+        };
+        let original = Memory::from(disk_map.clone());
+        let policies: [&dyn CompactionPolicy; 4] =
+            [&BlockFragmenting, &FirstFit, &BestFit, &WorstFit];
+        for policy in policies {
+            let mut memory = Memory::from(disk_map.clone());
+            memory.compact(policy);
+            assert!(memory.checksum() < original.checksum());
+            let before = files(&original);
+            for (file, (first, total)) in files(&memory) {
+                assert_eq!(total, before[&file].1);
+                assert!(first <= before[&file].0);
+            }
+        }
+    }
+
+    #[test]
+    fn render_and_encode_round_trip() {
+        let disk_map = Day09::parse(EXAMPLE).unwrap();
+        let memory = Memory::from(disk_map.clone());
+        assert_eq!(
+            memory.to_string(),
+            "00...111...2...333.44.5555.6666.777.888899"
+        );
+        assert_eq!(memory.disk_map(), disk_map);
+        let generated = generated_disk_map(false);
+        assert_eq!(Memory::from(generated.clone()).disk_map(), generated);
+        assert_eq!(
+            Memory::from(vec![2, 0, 3, 12, 1]).disk_map(),
+            [2, 0, 3, 9, 0, 3, 1]
+        );
+    }
+
+    #[test]
+    fn compacted_layouts() {
+        let disk_map = Day09::parse(EXAMPLE).unwrap();
+        let layout = |memory: &Memory| memory.to_string().replace(|ch| ch != '.', "#");
+        for (policy, blocks) in [
+            (
+                &BlockFragmenting as &dyn CompactionPolicy,
+                "0099811188827773336446555566..............",
+            ),
+            (&FirstFit, "00992111777.44.333....5555.6666.....8888.."),
+        ] {
+            let mut memory = Memory::from(disk_map.clone());
+            memory.compact(policy);
+            assert_eq!(memory.to_string(), blocks);
+            assert_eq!(layout(&Memory::from(memory.disk_map())), layout(&memory));
+        }
+    }
+
+    #[test]
+    fn fragmentation_report() {
+        let mut memory = Memory::from(Day09::parse(EXAMPLE).unwrap());
+        memory.compact(&FirstFit);
+        assert_eq!(
+            memory.fragmentation(),
+            Fragmentation {
+                free_spans: 5,
+                largest_free_span: 5,
+                unmoved_files: 6
+            }
+        );
+        let before = memory.to_string();
+        memory.compact(&BlockFragmenting);
+        assert_eq!(memory.fragmentation().free_spans, 0);
+        let count = |blocks: &str, digit| blocks.chars().filter(|&ch| ch == digit).count();
+        for digit in '0'..='9' {
+            assert_eq!(count(&memory.to_string(), digit), count(&before, digit));
+        }
+    }
+}
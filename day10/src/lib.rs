@@ -0,0 +1,88 @@
+use anyhow::Result;
+use aoc_common::{par::*, Grid, Pos, Solution};
+use std::collections::{HashMap, HashSet};
+
+fn trailheads(grid: &Grid<u32>) -> Vec<Pos> {
+    grid.iter()
+        .filter(|&(_, &height)| height == 0)
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+fn part1(grid: &Grid<u32>) -> usize {
+    trailheads(grid)
+        .into_par_iter()
+        .map(|pos| {
+            (1..=9)
+                .fold(HashSet::from([pos]), |positions, next_level| {
+                    positions
+                        .into_iter()
+                        .flat_map(|pos| grid.neighbors4(pos))
+                        .filter(|&pos| grid[pos] == next_level)
+                        .collect()
+                })
+                .len()
+        })
+        .sum()
+}
+
+fn part2(grid: &Grid<u32>) -> usize {
+    trailheads(grid)
+        .into_par_iter()
+        .map(|pos| {
+            (1..=9)
+                .fold(HashMap::from([(pos, 1)]), |positions, next_level| {
+                    positions
+                        .into_iter()
+                        .flat_map(|(pos, cnt)| grid.neighbors4(pos).map(move |pos| (pos, cnt)))
+                        .filter(|&(pos, _)| grid[pos] == next_level)
+                        .fold(HashMap::new(), |mut trails, (pos, count)| {
+                            *trails.entry(pos).or_insert(0) += count;
+                            trails
+                        })
+                })
+                .into_values()
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Grid<u32>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<u32>> {
+        Ok(Grid::parse_with(input, "a height", |ch| ch.to_digit(10))?)
+    }
+
+    fn part1(grid: &Grid<u32>) -> Result<usize> {
+        Ok(part1(grid))
+    }
+
+    fn part2(grid: &Grid<u32>) -> Result<usize> {
+        Ok(part2(grid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day10.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part1(&input).unwrap(), 36);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part2(&input).unwrap(), 81);
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::Result;
+use aoc_common::{parse, Param, Params, Solution};
+use lazy_static::lazy_static;
+use std::{collections::HashMap, sync::Mutex};
+
+type Stone = u64;
+
+lazy_static! {
+    static ref MEMO: Mutex<HashMap<Stone, HashMap<Stone, u64>>> = Mutex::new(HashMap::new());
+}
+
+fn iterate(stone: Stone) -> HashMap<Stone, u64> {
+    match stone {
+        0 => HashMap::from([(1, 1)]),
+        stone if stone.to_string().len() % 2 == 0 => {
+            let stone_str = stone.to_string();
+            let (s1, s2) = stone_str.split_at(stone_str.len() / 2);
+            let (s1, s2) = (s1.parse().unwrap(), s2.parse().unwrap());
+            let mut stones = HashMap::new();
+            *stones.entry(s1).or_default() += 1;
+            *stones.entry(s2).or_default() += 1;
+            stones
+        }
+        stone => HashMap::from([(stone * 2024, 1)]),
+    }
+}
+
+fn num_stones(stones: &[Stone], num_blinks: u64) -> u64 {
+    let mut grouped_stones =
+        stones
+            .iter()
+            .map(|stone| (stone, 1))
+            .fold(HashMap::new(), |mut hm, (&k, v)| {
+                *hm.entry(k).or_insert(0) += v;
+                hm
+            });
+    for _ in 0..num_blinks {
+        grouped_stones = grouped_stones
+            .into_iter()
+            .map(|(stone, cnt)| {
+                MEMO.lock()
+                    .unwrap()
+                    .entry(stone)
+                    .or_insert(iterate(stone))
+                    .iter()
+                    .map(|(&next_stone, &next_cnt)| (next_stone, next_cnt * cnt))
+                    .fold(HashMap::new(), |mut hm, (k, v)| {
+                        *hm.entry(k).or_insert(0) += v;
+                        hm
+                    })
+            })
+            .fold(HashMap::new(), |mut hm, next_stones| {
+                for (next_stone, next_cnt) in next_stones.into_iter() {
+                    *hm.entry(next_stone).or_insert(0) += next_cnt;
+                }
+                hm
+            });
+    }
+    // println!("{grouped_stones:?}");
+    grouped_stones.into_values().sum()
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Vec<Stone>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "blinks1",
+            default: 25,
+            example: 25,
+        },
+        Param {
+            name: "blinks2",
+            default: 75,
+            example: 75,
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Vec<Stone>> {
+        let mut lines = parse::lines(input);
+        let line = lines.expect("the stones")?;
+        let stones = line
+            .text
+            .split(' ')
+            .map(|stone| line.number(stone))
+            .collect::<Result<_, _>>()?;
+        if let Some(line) = lines.next() {
+            return Err(line.error(line.text, "the end of the input").into());
+        }
+        Ok(stones)
+    }
+
+    fn part1(stones: &Vec<Stone>) -> Result<u64> {
+        Self::part1_with(stones, &Self::defaults())
+    }
+
+    fn part2(stones: &Vec<Stone>) -> Result<u64> {
+        Self::part2_with(stones, &Self::defaults())
+    }
+
+    fn part1_with(stones: &Vec<Stone>, params: &Params) -> Result<u64> {
+        Ok(num_stones(stones, params.get("blinks1")?))
+    }
+
+    fn part2_with(stones: &Vec<Stone>, params: &Params) -> Result<u64> {
+        Ok(num_stones(stones, params.get("blinks2")?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day11.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part1(&input).unwrap(), 55312);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part2(&input).unwrap(), 65601038650482);
+    }
+}
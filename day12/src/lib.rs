@@ -0,0 +1,122 @@
+use anyhow::Result;
+use aoc_common::{Dir, Grid, Pos, Solution};
+use std::collections::{HashMap, HashSet};
+
+type Fences = HashMap<Dir, Vec<Pos>>;
+
+fn dfs<Visitor>(grid: &Grid<char>, pos: Pos, visited: &mut HashSet<Pos>, visitor: &mut Visitor)
+where
+    Visitor: FnMut(Pos),
+{
+    if !visited.insert(pos) {
+        return;
+    }
+    visitor(pos);
+    let ch = grid.get(pos);
+    Dir::ORTHOGONAL
+        .into_iter()
+        .filter(|&dir| grid.get(pos + dir) == ch)
+        .for_each(|dir| dfs(grid, pos + dir, visited, visitor));
+}
+
+fn part1(grid: &Grid<char>) -> i32 {
+    let mut visited = HashSet::new();
+    grid.positions()
+        .map(|pos| {
+            let (mut area, mut perimeter) = (0, 0);
+            dfs(grid, pos, &mut visited, &mut |pos| {
+                area += 1;
+                let curr = grid.get(pos);
+                perimeter += pos
+                    .neighbors4()
+                    .map(|neighbor| grid.get(neighbor))
+                    .filter(|&neighbor| curr != neighbor)
+                    .count() as i32;
+            });
+            area * perimeter
+        })
+        .sum()
+}
+
+fn part2(grid: &Grid<char>) -> i32 {
+    let mut visited = HashSet::new();
+    grid.positions()
+        .map(|pos| {
+            let mut area = 0;
+            let mut fences = Fences::new();
+            dfs(grid, pos, &mut visited, &mut |pos| {
+                let curr = grid.get(pos);
+                for dir in Dir::ORTHOGONAL
+                    .into_iter()
+                    .filter(|&dir| grid.get(pos + dir) != curr)
+                {
+                    fences.entry(dir).or_default().push(pos);
+                }
+                area += 1;
+            });
+            let sides = fences
+                .into_iter()
+                .map(|(dir, mut positions)| {
+                    if dir.delta().r == 0 {
+                        positions.sort_by(|p1, p2| p1.c.cmp(&p2.c).then(p1.r.cmp(&p2.r)));
+                        (1..positions.len())
+                            .map(|idx| (positions[idx - 1], positions[idx]))
+                            .filter(|(p1, p2)| p2.c != p1.c || p2.r - p1.r != 1)
+                            .count() as i32
+                            + 1
+                    } else {
+                        positions.sort();
+                        (1..positions.len())
+                            .map(|idx| (positions[idx - 1], positions[idx]))
+                            .filter(|(p1, p2)| p2.r != p1.r || p2.c - p1.c != 1)
+                            .count() as i32
+                            + 1
+                    }
+                })
+                .sum::<i32>();
+            area * sides
+        })
+        .sum()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Grid<char>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Grid<char>> {
+        Ok(Grid::parse_with(input, "a plant letter", |ch| {
+            ch.is_ascii_uppercase().then_some(ch)
+        })?)
+    }
+
+    fn part1(grid: &Grid<char>) -> Result<i32> {
+        Ok(part1(grid))
+    }
+
+    fn part2(grid: &Grid<char>) -> Result<i32> {
+        Ok(part2(grid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day12.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(Day12::part1(&input).unwrap(), 1930);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(Day12::part2(&input).unwrap(), 1206);
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::{bail, Result};
+use aoc_common::{
+    parse::{self, Line},
+    Param, Params, ParseError, Solution,
+};
+
+type Config = (i64, i64, i64, i64, i64, i64);
+type Input = Vec<Config>;
+
+/// How many times to press A and B to reach the prize moved by `offset`, if whole numbers of
+/// presses do. Buttons moving in the same direction would need a search this does not do.
+fn presses(&(x0, y0, x1, y1, xt, yt): &Config, offset: i64) -> Result<Option<(i64, i64)>> {
+    let det = x0 * y1 - y0 * x1;
+    if det == 0 {
+        bail!("buttons A (X+{x0}, Y+{y0}) and B (X+{x1}, Y+{y1}) move in the same direction");
+    }
+    let xt = xt + offset;
+    let yt = yt + offset;
+    let (n0, r0) = num::integer::div_rem(xt * y1 - yt * x1, det);
+    let (n1, r1) = num::integer::div_rem(xt * y0 - yt * x0, -det);
+    Ok((r0 == 0 && r1 == 0).then_some((n0, n1)))
+}
+
+fn part1(input: &Input) -> Result<i64> {
+    input
+        .iter()
+        .map(|config| match presses(config, 0)? {
+            Some((n0, n1)) if n0 <= 100 && n1 <= 100 => Ok(n0 * 3 + n1),
+            _ => Ok(0),
+        })
+        .sum()
+}
+
+fn part2(input: &Input, offset: i64) -> Result<i64> {
+    input
+        .iter()
+        .map(|config| Ok(presses(config, offset)?.map_or(0, |(n0, n1)| n0 * 3 + n1)))
+        .sum()
+}
+
+fn coordinates(line: Line, prefix: &str, separator: &str) -> Result<(i64, i64), ParseError> {
+    let (x, y) = line.split_once(line.strip_prefix(line.text, prefix)?, separator)?;
+    Ok((line.number(x)?, line.number(y)?))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = Input;
+    type Answer1 = i64;
+    type Answer2 = i64;
+    const PARAMS: &'static [Param] = &[Param {
+        name: "offset",
+        default: 10000000000000,
+        example: 10000000000000,
+    }];
+
+    fn parse(input: &str) -> Result<Input> {
+        let mut lines = parse::lines(input);
+        let mut configs = Input::new();
+        loop {
+            let (ax, ay) = coordinates(lines.expect("button A")?, "Button A: X+", ", Y+")?;
+            let (bx, by) = coordinates(lines.expect("button B")?, "Button B: X+", ", Y+")?;
+            let (px, py) = coordinates(lines.expect("a prize")?, "Prize: X=", ", Y=")?;
+            configs.push((ax, ay, bx, by, px, py));
+            match lines.next() {
+                None => return Ok(configs),
+                Some(line) => line.end(line.text)?,
+            }
+        }
+    }
+
+    fn part1(input: &Input) -> Result<i64> {
+        part1(input)
+    }
+
+    fn part2(input: &Input) -> Result<i64> {
+        Self::part2_with(input, &Self::defaults())
+    }
+
+    fn part2_with(input: &Input, params: &Params) -> Result<i64> {
+        part2(input, params.get("offset")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day13.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(Day13::part1(&input).unwrap(), 480);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(Day13::part2(&input).unwrap(), 875318608908);
+    }
+
+    #[test]
+    fn parallel_buttons_are_reported() {
+        let input =
+            Day13::parse("Button A: X+1, Y+1\nButton B: X+2, Y+2\nPrize: X=3, Y=3\n").unwrap();
+        assert!(Day13::part1(&input).is_err());
+        assert!(Day13::part2(&input).is_err());
+    }
+
+    #[test]
+    fn truncated_machine_is_reported() {
+        let err = Day13::parse("Button A: X+94, Y+34\nButton B: X+22, Y+67\n").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.expected.as_str()), (3, "a prize"));
+    }
+}
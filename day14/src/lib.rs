@@ -0,0 +1,155 @@
+use anyhow::{Context, Result};
+use aoc_common::{
+    parse,
+    render::{Frame, Recorder, BLACK, GREEN},
+    Param, Params, ParseError, Pos, Solution,
+};
+use std::collections::{HashMap, HashSet};
+
+type Velocity = Pos;
+
+fn part1(input: &[(Pos, Velocity)], x_max: i32, y_max: i32) -> i32 {
+    let x_mid = x_max / 2;
+    let y_mid = y_max / 2;
+    input
+        .iter()
+        .map(|&(p, v)| (p + v * 100) % Pos::new(y_max, x_max))
+        .filter_map(|p| match p {
+            Pos { r: y, c: x } if x < x_mid && y < y_mid => Some(0),
+            Pos { r: y, c: x } if x > x_mid && y < y_mid => Some(1),
+            Pos { r: y, c: x } if x < x_mid && y > y_mid => Some(2),
+            Pos { r: y, c: x } if x > x_mid && y > y_mid => Some(3),
+            _ => None,
+        })
+        .fold(HashMap::<i32, i32>::new(), |mut hm, q| {
+            *hm.entry(q).or_default() += 1;
+            hm
+        })
+        .into_values()
+        .product()
+}
+
+/// The first second at which no two robots overlap. Every robot is back where it started after
+/// `x_max * y_max` seconds, so there is none if it has not happened by then.
+fn first_apart(input: &[(Pos, Velocity)], x_max: i32, y_max: i32) -> Option<i32> {
+    (0..x_max * y_max).find(|&n| {
+        let robots = input
+            .iter()
+            .map(|&(p, v)| (p + v * n) % Pos::new(y_max, x_max))
+            .collect::<HashSet<_>>();
+        robots.len() == input.len()
+    })
+}
+
+fn part2(input: &[(Pos, Velocity)], x_max: i32, y_max: i32) -> Result<i32> {
+    first_apart(input, x_max, y_max).with_context(|| {
+        format!(
+            "no second separates every robot in a {x_max} by {y_max} room, and after {} \
+             seconds they repeat",
+            x_max * y_max
+        )
+    })
+}
+
+fn robots_after(input: &[(Pos, Velocity)], seconds: i32, x_max: i32, y_max: i32) -> Frame {
+    let mut frame = Frame::new(y_max as usize, x_max as usize, BLACK);
+    frame.paint_all(
+        input
+            .iter()
+            .map(|&(p, v)| (p + v * seconds) % Pos::new(y_max, x_max)),
+        GREEN,
+    );
+    frame
+}
+
+/// The first 100 seconds, then the first second at which no two robots overlap, which on the
+/// real input is the picture part two looks for.
+fn render(
+    input: &[(Pos, Velocity)],
+    x_max: i32,
+    y_max: i32,
+    recorder: &mut Recorder,
+) -> Result<()> {
+    for seconds in 0..=100 {
+        recorder.record(|| robots_after(input, seconds, x_max, y_max))?;
+    }
+    match first_apart(input, x_max, y_max) {
+        Some(seconds) => recorder.save(&robots_after(input, seconds, x_max, y_max)),
+        None => recorder.finish(|| robots_after(input, 100, x_max, y_max)),
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = Vec<(Pos, Velocity)>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "width",
+            default: 101,
+            example: 11,
+        },
+        Param {
+            name: "height",
+            default: 103,
+            example: 7,
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input)
+            .map(|line| {
+                let (p, v) = line.split_once(line.text, " ")?;
+                let (px, py) = line.split_once(line.strip_prefix(p, "p=")?, ",")?;
+                let (vx, vy) = line.split_once(line.strip_prefix(v, "v=")?, ",")?;
+                Ok((
+                    Pos::new(line.number(py)?, line.number(px)?),
+                    Velocity::new(line.number(vy)?, line.number(vx)?),
+                ))
+            })
+            .collect::<Result<_, ParseError>>()?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<i32> {
+        Self::part1_with(input, &Self::defaults())
+    }
+
+    fn part2(input: &Self::Input) -> Result<i32> {
+        Self::part2_with(input, &Self::defaults())
+    }
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Result<i32> {
+        Ok(part1(input, params.get("width")?, params.get("height")?))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Result<i32> {
+        part2(input, params.get("width")?, params.get("height")?)
+    }
+
+    fn render(input: &Self::Input, params: &Params, recorder: &mut Recorder) -> Result<()> {
+        render(input, params.get("width")?, params.get("height")?, recorder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day14.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input, 11, 7), 12);
+    }
+
+    #[test]
+    fn part2_without_a_picture() {
+        let input = Day14::parse("p=0,0 v=1,1\np=0,0 v=1,1").unwrap();
+        assert!(part2(&input, 11, 7).is_err());
+        assert_eq!(part2(&input[..1], 11, 7).unwrap(), 0);
+    }
+}
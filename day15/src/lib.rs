@@ -0,0 +1,218 @@
+use anyhow::Result;
+use aoc_common::{
+    parse,
+    render::{Frame, Recorder, BLACK, BROWN, GREY, YELLOW},
+    Dir, Grid, Params, Pos, Solution,
+};
+
+fn find_robot(grid: &Grid<char>) -> Pos {
+    grid.find(&'@').unwrap()
+}
+
+fn part1(grid: Grid<char>, moves: &[Dir]) -> i32 {
+    let robot = find_robot(&grid);
+    let (grid, _) = moves
+        .iter()
+        .fold((grid, robot), |(mut grid, mut robot), &mov| {
+            if do_move(&mut grid, robot, mov) {
+                robot += mov;
+            }
+            (grid, robot)
+        });
+    grid.iter()
+        .filter(|&(_, &ch)| ch == 'O')
+        .map(|(Pos { r, c }, _)| 100 * r + c)
+        .sum()
+}
+
+/// The tile at `p`, with anything outside the grid a wall, for maps without a border.
+fn tile(grid: &Grid<char>, p: Pos) -> char {
+    grid.get(p).copied().unwrap_or('#')
+}
+
+fn check_move(grid: &mut Grid<char>, p: Pos, d: Dir) -> bool {
+    let ch = tile(grid, p);
+    let next = p + d;
+    match ch {
+        '.' => true,
+        '#' => false,
+        'O' | '@' => check_move(grid, next, d),
+        '[' => check_move(grid, next, d) && check_move(grid, p + Dir::Right + d, d),
+        ']' => check_move(grid, next, d) && check_move(grid, p + Dir::Left + d, d),
+        _ => panic!(),
+    }
+}
+
+fn do_move(grid: &mut Grid<char>, p: Pos, d: Dir) -> bool {
+    let ch = tile(grid, p);
+    let next = p + d;
+    match ch {
+        '.' => true,
+        '#' => false,
+        'O' | '@' => {
+            if do_move(grid, next, d) {
+                grid[next] = ch;
+                grid[p] = '.';
+                return true;
+            }
+            false
+        }
+        '[' | ']' => {
+            let pair = p + if ch == '[' { Dir::Right } else { Dir::Left };
+            let pair_next = pair + d;
+            let pair_ch = if ch == '[' { ']' } else { '[' };
+            if pair == next {
+                if do_move(grid, next + d, d) {
+                    grid[pair_next] = pair_ch;
+                    grid[next] = ch;
+                    grid[p] = '.';
+                    return true;
+                }
+            } else {
+                if check_move(grid, next, d) && check_move(grid, pair_next, d) {
+                    do_move(grid, next, d);
+                    grid[next] = ch;
+                    grid[p] = '.';
+                    do_move(grid, pair_next, d);
+                    grid[pair_next] = pair_ch;
+                    grid[pair] = '.';
+                    return true;
+                }
+            }
+            false
+        }
+        _ => panic!(),
+    }
+}
+
+fn part2(grid: Grid<char>, moves: &[Dir]) -> i32 {
+    let robot = find_robot(&grid);
+    let (grid, _) = moves
+        .iter()
+        .fold((grid, robot), |(mut grid, mut robot), &mov| {
+            if do_move(&mut grid, robot, mov) {
+                robot += mov;
+            }
+            (grid, robot)
+        });
+    grid.iter()
+        .filter(|&(_, &ch)| ch == '[')
+        .map(|(Pos { r, c }, _)| 100 * r + c)
+        .sum()
+}
+
+fn widen(grid: &Grid<char>) -> Grid<char> {
+    grid.rows()
+        .map(|row| {
+            row.iter()
+                .flat_map(|ch| match ch {
+                    '.' => ['.', '.'],
+                    'O' => ['[', ']'],
+                    '#' => ['#', '#'],
+                    '@' => ['@', '.'],
+                    _ => panic!(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn frame(grid: &Grid<char>) -> Frame {
+    Frame::from_grid(grid, |ch| match ch {
+        '#' => GREY,
+        'O' | '[' | ']' => BROWN,
+        '@' => YELLOW,
+        _ => BLACK,
+    })
+}
+
+/// The widened warehouse of part two, a frame per move.
+fn render(mut grid: Grid<char>, moves: &[Dir], recorder: &mut Recorder) -> Result<()> {
+    let mut robot = find_robot(&grid);
+    recorder.record(|| frame(&grid))?;
+    for &mov in moves {
+        if do_move(&mut grid, robot, mov) {
+            robot += mov;
+        }
+        recorder.record(|| frame(&grid))?;
+    }
+    recorder.finish(|| frame(&grid))
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = (Grid<char>, Vec<Dir>);
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = parse::lines(input);
+        let grid = Grid::parse_lines(lines.block(), "'#', '.', 'O' or '@'", |ch| {
+            matches!(ch, '#' | '.' | 'O' | '@').then_some(ch)
+        })?;
+        if grid.find(&'@').is_none() {
+            return Err(lines.end_error("a '@' tile").into());
+        }
+        let mut moves = Vec::new();
+        for line in lines {
+            for (offset, ch) in line.text.char_indices() {
+                let dir = Dir::from_arrow(ch).ok_or_else(|| {
+                    line.error(&line.text[offset..offset + ch.len_utf8()], "a move")
+                })?;
+                moves.push(dir);
+            }
+        }
+        Ok((grid, moves))
+    }
+
+    fn part1((grid, moves): &Self::Input) -> Result<i32> {
+        Ok(part1(grid.clone(), moves))
+    }
+
+    fn part2((grid, moves): &Self::Input) -> Result<i32> {
+        Ok(part2(widen(grid), moves))
+    }
+
+    fn render((grid, moves): &Self::Input, _: &Params, recorder: &mut Recorder) -> Result<()> {
+        render(widen(grid), moves, recorder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::ParseError;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day15.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day15::parse(EXAMPLE).unwrap();
+        assert_eq!(Day15::part1(&input).unwrap(), 10092);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day15::parse(EXAMPLE).unwrap();
+        assert_eq!(Day15::part2(&input).unwrap(), 9021);
+    }
+
+    #[test]
+    fn no_border_wall() {
+        let input = Day15::parse("@O.\n\n>>>\n").unwrap();
+        assert_eq!(Day15::part1(&input).unwrap(), 2);
+        assert_eq!(Day15::part2(&input).unwrap(), 4);
+    }
+
+    #[test]
+    fn missing_robot_is_reported() {
+        let err = Day15::parse("###\n#O#\n###\n\n<^\n").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (4, 1, "a '@' tile")
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use anyhow::{Context, Result};
+use aoc_common::{
+    parse,
+    render::{Frame, Recorder, BLACK, GREEN, GREY, YELLOW},
+    search::{self, Paths},
+    Dir, Grid, Params, Pos, Solution,
+};
+use itertools::Itertools;
+
+type State = (Pos, Dir);
+
+fn paths_from(grid: &Grid<char>, start: State) -> Paths<State, i32> {
+    search::dijkstra(start, |&(pos, dir)| {
+        // Anything outside the grid is wall, for maps without a border.
+        let open = grid.get(pos + dir).is_some_and(|&ch| ch != '#');
+        let forward = open.then_some(((pos + dir, dir), 1));
+        let turns = [dir.turn_left(), dir.turn_right()].map(|dir| ((pos, dir), 1000));
+        forward.into_iter().chain(turns)
+    })
+}
+
+/// The states facing each way on `end` that are reached at the lowest cost, and that cost, or
+/// `None` if `end` cannot be reached.
+fn best_ends(paths: &Paths<State, i32>, end: Pos) -> Option<(i32, Vec<State>)> {
+    let ends = Dir::ORTHOGONAL.map(|dir| (end, dir));
+    let best = ends.iter().filter_map(|end| paths.cost(end)).min()?;
+    let ends = ends.into_iter().filter(|end| paths.cost(end) == Some(best));
+    Some((best, ends.collect()))
+}
+
+fn part1(grid: &Grid<char>) -> Result<i32> {
+    let start_position = grid.find(&'S').unwrap();
+    let end_position = grid.find(&'E').unwrap();
+    let paths = paths_from(grid, (start_position, Dir::Right));
+    let (best, _) = best_ends(&paths, end_position).context("the end cannot be reached")?;
+    Ok(best)
+}
+
+fn part2(grid: &Grid<char>) -> Result<usize> {
+    let start_position = grid.find(&'S').unwrap();
+    let end_position = grid.find(&'E').unwrap();
+    let paths = paths_from(grid, (start_position, Dir::Right));
+    let (_, ends) = best_ends(&paths, end_position).context("the end cannot be reached")?;
+    Ok(paths
+        .states_on_paths(ends)
+        .into_iter()
+        .map(|(pos, _)| pos)
+        .unique()
+        .count())
+}
+
+/// The reindeer walking one of the cheapest paths, then every tile on any of them in green.
+fn render(grid: &Grid<char>, recorder: &mut Recorder) -> Result<()> {
+    let start_position = grid.find(&'S').context("the maze has no start")?;
+    let end_position = grid.find(&'E').context("the maze has no end")?;
+    let paths = paths_from(grid, (start_position, Dir::Right));
+    let (_, ends) = best_ends(&paths, end_position).context("the end cannot be reached")?;
+    let path = paths
+        .path_to(&ends[0])
+        .context("the end cannot be reached")?;
+    let mut frame = Frame::from_grid(grid, |&ch| if ch == '#' { GREY } else { BLACK });
+    let mut previous = None;
+    for (pos, _) in path.into_iter().dedup_by(|a, b| a.0 == b.0) {
+        frame.paint_all(previous, GREEN);
+        frame.paint(pos, YELLOW);
+        recorder.record(|| frame.clone())?;
+        previous = Some(pos);
+    }
+    frame.paint_all(
+        paths.states_on_paths(ends).into_iter().map(|(pos, _)| pos),
+        GREEN,
+    );
+    recorder.save(&frame)
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Input = Grid<char>;
+    type Answer1 = i32;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<char>> {
+        let mut lines = parse::lines(input);
+        let grid = Grid::parse_lines(lines.by_ref(), "'.', '#', 'S' or 'E'", |ch| {
+            matches!(ch, '.' | '#' | 'S' | 'E').then_some(ch)
+        })?;
+        for tile in ['S', 'E'] {
+            if grid.find(&tile).is_none() {
+                return Err(lines.end_error(format_args!("a {tile:?} tile")).into());
+            }
+        }
+        Ok(grid)
+    }
+
+    fn part1(grid: &Grid<char>) -> Result<i32> {
+        part1(grid)
+    }
+
+    fn part2(grid: &Grid<char>) -> Result<usize> {
+        part2(grid)
+    }
+
+    fn render(grid: &Grid<char>, _: &Params, recorder: &mut Recorder) -> Result<()> {
+        render(grid, recorder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::ParseError;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day16.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day16::parse(EXAMPLE).unwrap();
+        assert_eq!(Day16::part1(&input).unwrap(), 7036);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day16::parse(EXAMPLE).unwrap();
+        assert_eq!(Day16::part2(&input).unwrap(), 45);
+    }
+
+    #[test]
+    fn no_border_wall() {
+        let input = Day16::parse("SE").unwrap();
+        assert_eq!(Day16::part1(&input).unwrap(), 1);
+        assert_eq!(Day16::part2(&input).unwrap(), 2);
+    }
+
+    #[test]
+    fn unreachable_end() {
+        let input = Day16::parse("#####\n#S#E#\n#####").unwrap();
+        assert!(Day16::part1(&input).is_err());
+        assert!(Day16::part2(&input).is_err());
+    }
+
+    #[test]
+    fn missing_end_is_reported() {
+        let err = Day16::parse("###\n#S#\n###\n").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (3, 4, "a 'E' tile")
+        );
+    }
+}
@@ -0,0 +1,348 @@
+//! The machine's instruction set, listings of programs written in it, and an assembler that
+//! reads those listings back.
+
+use aoc_common::{
+    parse::{self, Line},
+    ParseError,
+};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Opcode {
+    Adv,
+    Bxl,
+    Bst,
+    Jnz,
+    Bxc,
+    Out,
+    Bdv,
+    Cdv,
+}
+
+impl Opcode {
+    /// In the order of their numbers.
+    pub const ALL: [Opcode; 8] = [
+        Opcode::Adv,
+        Opcode::Bxl,
+        Opcode::Bst,
+        Opcode::Jnz,
+        Opcode::Bxc,
+        Opcode::Out,
+        Opcode::Bdv,
+        Opcode::Cdv,
+    ];
+
+    pub fn new(cell: i64) -> Option<Opcode> {
+        let index = usize::try_from(cell).ok()?;
+        Opcode::ALL.get(index).copied()
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Adv => "adv",
+            Opcode::Bxl => "bxl",
+            Opcode::Bst => "bst",
+            Opcode::Jnz => "jnz",
+            Opcode::Bxc => "bxc",
+            Opcode::Out => "out",
+            Opcode::Bdv => "bdv",
+            Opcode::Cdv => "cdv",
+        }
+    }
+
+    /// Whether the operand is a combo operand, naming a register from 4 up, rather than a
+    /// literal. `bxc` reads its operand as neither.
+    pub fn takes_combo(self) -> bool {
+        matches!(
+            self,
+            Opcode::Adv | Opcode::Bst | Opcode::Out | Opcode::Bdv | Opcode::Cdv
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Register {
+    A,
+    B,
+    C,
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operand {
+    Literal(i64),
+    Register(Register),
+    /// Combo operand 7, which the machine reserves.
+    Reserved,
+}
+
+impl Operand {
+    /// How `opcode` reads `cell`.
+    pub fn new(opcode: Opcode, cell: i64) -> Operand {
+        match (opcode.takes_combo(), cell) {
+            (true, 4) => Operand::Register(Register::A),
+            (true, 5) => Operand::Register(Register::B),
+            (true, 6) => Operand::Register(Register::C),
+            (true, 7) => Operand::Reserved,
+            _ => Operand::Literal(cell),
+        }
+    }
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Literal(value) => write!(f, "{value}"),
+            Operand::Register(register) => write!(f, "{register}"),
+            Operand::Reserved => write!(f, "7"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub operand: Operand,
+}
+
+impl Instruction {
+    /// What the instruction does, in the notation of [`pseudocode`].
+    pub fn statement(&self) -> String {
+        let x = match self.operand {
+            Operand::Reserved => "?".to_string(),
+            operand => operand.to_string(),
+        };
+        match self.opcode {
+            Opcode::Adv => format!("A = A >> {x}"),
+            Opcode::Bxl => format!("B = B ^ {x}"),
+            Opcode::Bst if matches!(self.operand, Operand::Literal(_)) => format!("B = {x}"),
+            Opcode::Bst => format!("B = {x} % 8"),
+            Opcode::Jnz => format!("if A != 0 goto {x}"),
+            Opcode::Bxc => "B = B ^ C".to_string(),
+            Opcode::Out if matches!(self.operand, Operand::Literal(_)) => format!("out {x}"),
+            Opcode::Out => format!("out {x} % 8"),
+            Opcode::Bdv => format!("B = A >> {x}"),
+            Opcode::Cdv => format!("C = A >> {x}"),
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.opcode.mnemonic(), self.operand)
+    }
+}
+
+/// A piece of memory as the machine would read it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Decoded {
+    Instruction(Instruction),
+    /// Cells that do not make an instruction, such as an opcode with no operand after it.
+    Data(Vec<i64>),
+}
+
+impl Display for Decoded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Decoded::Instruction(instruction) => write!(f, "{instruction}"),
+            Decoded::Data(cells) => {
+                let cells = cells.iter().map(|cell| cell.to_string());
+                write!(f, ".data {}", cells.collect::<Vec<_>>().join(","))
+            }
+        }
+    }
+}
+
+/// The instruction at `address`, which must be inside `memory`.
+pub fn decode(memory: &[i64], address: usize) -> Decoded {
+    let cells = &memory[address..memory.len().min(address + 2)];
+    match (Opcode::new(cells[0]), cells.get(1)) {
+        (Some(opcode), Some(&cell)) => Decoded::Instruction(Instruction {
+            opcode,
+            operand: Operand::new(opcode, cell),
+        }),
+        _ => Decoded::Data(cells.to_vec()),
+    }
+}
+
+/// Decodes `memory` from address 0, two cells at a time, with the address of each piece.
+pub fn disassemble(memory: &[i64]) -> Vec<(usize, Decoded)> {
+    (0..memory.len())
+        .step_by(2)
+        .map(|address| (address, decode(memory, address)))
+        .collect()
+}
+
+/// One line per instruction, with its address and what it does in a comment.
+pub fn listing(memory: &[i64]) -> String {
+    let decoded = disassemble(memory);
+    let width = memory.len().saturating_sub(1).to_string().len();
+    decoded
+        .iter()
+        .map(|(address, decoded)| {
+            let statement = match decoded {
+                Decoded::Instruction(instruction) => instruction.statement(),
+                Decoded::Data(_) => "not an instruction".to_string(),
+            };
+            format!(
+                "{:<10}; {address:>width$}: {statement}\n",
+                decoded.to_string()
+            )
+        })
+        .collect()
+}
+
+/// The program as structured code: each backward `jnz` becomes a `do { .. } while A != 0` loop
+/// around the instructions it jumps back over, so long as the loops nest. Other jumps stay as
+/// `goto`s to labels.
+pub fn pseudocode(memory: &[i64]) -> String {
+    let decoded = disassemble(memory);
+    let is_address = |target: i64| {
+        usize::try_from(target).is_ok_and(|target| target % 2 == 0 && target < memory.len())
+    };
+    let jumps = decoded
+        .iter()
+        .filter_map(|(address, decoded)| match decoded {
+            Decoded::Instruction(Instruction {
+                opcode: Opcode::Jnz,
+                operand: Operand::Literal(target),
+            }) if is_address(*target) => Some((*target as usize, *address)),
+            _ => None,
+        });
+    let mut loops = Vec::<(usize, usize)>::new();
+    let mut labels = Vec::new();
+    for (target, address) in jumps {
+        let nests = loops.iter().all(|&(start, end)| {
+            address < start || target > end || (target <= start && address >= end)
+        });
+        if target <= address && nests {
+            loops.push((target, address));
+        } else {
+            labels.push(target);
+        }
+    }
+    let indent = |depth: usize| "    ".repeat(depth);
+    let mut code = String::new();
+    let mut depth = 0;
+    for (address, decoded) in &decoded {
+        if labels.contains(address) {
+            code += &format!("{}L{address}:\n", indent(depth));
+        }
+        for _ in loops.iter().filter(|&&(start, _)| start == *address) {
+            code += &format!("{}do {{\n", indent(depth));
+            depth += 1;
+        }
+        if loops.iter().any(|&(_, end)| end == *address) {
+            depth -= 1;
+            code += &format!("{}}} while A != 0\n", indent(depth));
+            continue;
+        }
+        let line = match decoded {
+            Decoded::Instruction(Instruction {
+                opcode: Opcode::Jnz,
+                operand: Operand::Literal(target),
+            }) if labels.contains(&(*target as usize)) => format!("if A != 0 goto L{target}"),
+            Decoded::Instruction(instruction) => instruction.statement(),
+            Decoded::Data(cells) => format!("// not an instruction: {cells:?}"),
+        };
+        code += &format!("{}{line}\n", indent(depth));
+    }
+    code
+}
+
+/// Assembles one instruction per line, such as `loop: bst A` or `jnz loop`, into memory. A `;`
+/// starts a comment, `name:` labels the address of whatever follows it and `.data 1,2` places
+/// raw cells, so the output of [`listing`] assembles back to the same memory.
+pub fn assemble(source: &str) -> Result<Vec<i64>, ParseError> {
+    assemble_lines(parse::lines(source))
+}
+
+pub(crate) fn assemble_lines<'a>(
+    lines: impl IntoIterator<Item = Line<'a>>,
+) -> Result<Vec<i64>, ParseError> {
+    // Lay the statements out first, so that a jump can name a label further down.
+    let mut labels = HashMap::new();
+    let mut statements = Vec::new();
+    let mut address = 0;
+    for line in lines {
+        let code = line.text.split(';').next().unwrap_or_default();
+        let mut rest = code.trim();
+        while let Some((label, after)) = rest.split_once(':') {
+            let label = label.trim();
+            if !is_label(label) {
+                return Err(line.error(label, "a label"));
+            }
+            if labels.insert(label, address).is_some() {
+                return Err(line.error(label, "a label not already defined"));
+            }
+            rest = after.trim();
+        }
+        if rest.is_empty() {
+            continue;
+        }
+        let (mnemonic, operand) = match rest.split_once(char::is_whitespace) {
+            Some((mnemonic, operand)) => (mnemonic, operand.trim()),
+            None => (rest, &rest[rest.len()..]),
+        };
+        address += if mnemonic == ".data" {
+            operand.split(',').count()
+        } else {
+            2
+        };
+        statements.push((line, mnemonic, operand));
+    }
+    let mut memory = Vec::with_capacity(address);
+    for (line, mnemonic, operand) in statements {
+        if mnemonic == ".data" {
+            for value in operand.split(',') {
+                memory.push(cell(&line, value.trim(), "a 3-bit number")?);
+            }
+            continue;
+        }
+        let opcode = Opcode::ALL
+            .into_iter()
+            .find(|opcode| opcode.mnemonic() == mnemonic)
+            .ok_or_else(|| line.error(mnemonic, "an instruction or .data"))?;
+        let operand = match (opcode, operand) {
+            (_, "A") if opcode.takes_combo() => 4,
+            (_, "B") if opcode.takes_combo() => 5,
+            (_, "C") if opcode.takes_combo() => 6,
+            _ if opcode.takes_combo() => cell(&line, operand, "a register or a 3-bit number")?,
+            (Opcode::Jnz, _) if is_label(operand) => {
+                let &target = labels
+                    .get(operand)
+                    .ok_or_else(|| line.error(operand, "a defined label"))?;
+                if target > 7 {
+                    return Err(line.error(operand, "a label within the first 8 cells"));
+                }
+                target as i64
+            }
+            (Opcode::Jnz, _) => cell(&line, operand, "a label or a 3-bit number")?,
+            _ => cell(&line, operand, "a 3-bit number")?,
+        };
+        memory.extend([opcode as i64, operand]);
+    }
+    Ok(memory)
+}
+
+fn is_label(text: &str) -> bool {
+    text.starts_with(|ch: char| ch.is_ascii_alphabetic() || ch == '_')
+        && text
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+pub(crate) fn cell<'a>(line: &Line<'a>, text: &'a str, expected: &str) -> Result<i64, ParseError> {
+    match line.parse(text, expected)? {
+        cell @ 0..=7 => Ok(cell),
+        _ => Err(line.error(text, expected)),
+    }
+}
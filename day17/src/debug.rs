@@ -0,0 +1,169 @@
+//! Running a program one instruction at a time, with a trace of every step, a step limit, and
+//! breakpoints and watchpoints to stop at.
+
+use crate::{
+    asm::{self, Decoded, Register},
+    State, VmError,
+};
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display},
+};
+
+/// One executed instruction and the registers it left behind.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    /// Counting from 1.
+    pub number: usize,
+    pub pointer: usize,
+    pub instruction: Decoded,
+    pub registers: [i64; 3],
+    pub output: Option<i64>,
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c] = self.registers;
+        write!(
+            f,
+            "{:>6}  {:>3}: {:<8}  A={a} B={b} C={c}",
+            self.number,
+            self.pointer,
+            self.instruction.to_string()
+        )?;
+        if let Some(output) = self.output {
+            write!(f, "  out {output}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Why [`Debugger::run`] returned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stop {
+    Halted,
+    /// About to execute the instruction at a breakpoint.
+    Breakpoint {
+        pointer: usize,
+    },
+    /// The last step changed a watched register.
+    Watchpoint {
+        register: Register,
+        old: i64,
+        new: i64,
+    },
+}
+
+impl Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stop::Halted => write!(f, "halted"),
+            Stop::Breakpoint { pointer } => write!(f, "breakpoint at {pointer}"),
+            Stop::Watchpoint { register, old, new } => {
+                write!(f, "watchpoint: {register} changed from {old} to {new}")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Debugger {
+    /// Steps after which [`Debugger::run`] gives up with [`VmError::DidNotHalt`].
+    pub limit: usize,
+    /// Addresses to stop before executing.
+    pub breakpoints: BTreeSet<usize>,
+    /// Registers to stop after any step that changes them.
+    pub watches: Vec<Register>,
+    state: State,
+    steps: usize,
+    output: Vec<i64>,
+    /// Whether the last stop was at the breakpoint under the pointer, which resuming passes.
+    at_breakpoint: bool,
+}
+
+impl Debugger {
+    pub fn new(state: State) -> Self {
+        Debugger {
+            limit: usize::MAX,
+            breakpoints: BTreeSet::new(),
+            watches: Vec::new(),
+            state,
+            steps: 0,
+            output: Vec::new(),
+            at_breakpoint: false,
+        }
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Everything output so far.
+    pub fn output(&self) -> &[i64] {
+        &self.output
+    }
+
+    pub fn into_output(self) -> Vec<i64> {
+        self.output
+    }
+
+    /// Executes one instruction, or returns `None` if the program has halted.
+    pub fn step(&mut self) -> Result<Option<Step>, VmError> {
+        if self.state.is_halted() {
+            return Ok(None);
+        }
+        let pointer = self.state.pointer();
+        let instruction = asm::decode(self.state.program(), pointer);
+        let output = self.state.advance()?;
+        self.steps += 1;
+        self.at_breakpoint = false;
+        self.output.extend(output);
+        Ok(Some(Step {
+            number: self.steps,
+            pointer,
+            instruction,
+            registers: [Register::A, Register::B, Register::C]
+                .map(|register| self.state.register(register)),
+            output,
+        }))
+    }
+
+    /// Steps until the program halts or reaches a breakpoint or watchpoint, passing each step to
+    /// `trace`. Running again resumes where it stopped.
+    pub fn run(&mut self, mut trace: impl FnMut(&Step)) -> Result<Stop, VmError> {
+        loop {
+            let pointer = self.state.pointer();
+            if self.breakpoints.contains(&pointer) && !self.at_breakpoint {
+                self.at_breakpoint = true;
+                return Ok(Stop::Breakpoint { pointer });
+            }
+            if self.steps == self.limit && !self.state.is_halted() {
+                return Err(VmError::DidNotHalt { steps: self.steps });
+            }
+            let before = self
+                .watches
+                .iter()
+                .map(|&register| self.state.register(register))
+                .collect::<Vec<_>>();
+            let Some(step) = self.step()? else {
+                return Ok(Stop::Halted);
+            };
+            trace(&step);
+            let changed = self
+                .watches
+                .iter()
+                .zip(before)
+                .find_map(|(&register, old)| {
+                    let new = self.state.register(register);
+                    (new != old).then_some(Stop::Watchpoint { register, old, new })
+                });
+            if let Some(stop) = changed {
+                return Ok(stop);
+            }
+        }
+    }
+}
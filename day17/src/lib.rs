@@ -0,0 +1,515 @@
+pub mod asm;
+pub mod debug;
+pub mod sat;
+pub mod symbolic;
+
+use anyhow::Result;
+use aoc_common::{parse, Param, Params, ParseError, Solution};
+use asm::{Opcode, Register};
+use debug::Debugger;
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    fmt::{self, Display},
+    iter,
+};
+
+#[derive(Debug, Clone)]
+pub struct State {
+    reg_a: i64,
+    reg_b: i64,
+    reg_c: i64,
+    memory: Vec<i64>,
+    pointer: usize,
+}
+
+impl State {
+    /// The machine about to run `memory` from its first cell.
+    pub fn new(reg_a: i64, reg_b: i64, reg_c: i64, memory: Vec<i64>) -> Self {
+        State {
+            reg_a,
+            reg_b,
+            reg_c,
+            memory,
+            pointer: 0,
+        }
+    }
+
+    pub fn register(&self, register: Register) -> i64 {
+        match register {
+            Register::A => self.reg_a,
+            Register::B => self.reg_b,
+            Register::C => self.reg_c,
+        }
+    }
+
+    pub fn set_register(&mut self, register: Register, value: i64) {
+        match register {
+            Register::A => self.reg_a = value,
+            Register::B => self.reg_b = value,
+            Register::C => self.reg_c = value,
+        }
+    }
+
+    /// The address of the next instruction.
+    pub fn pointer(&self) -> usize {
+        self.pointer
+    }
+
+    /// The program in memory, as the cells after `Program: `.
+    pub fn program(&self) -> &[i64] {
+        &self.memory
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pointer >= self.memory.len()
+    }
+
+    fn instruction(&self) -> Result<Opcode, VmError> {
+        let opcode = self.memory[self.pointer];
+        Opcode::new(opcode).ok_or(VmError::InvalidOpcode {
+            pointer: self.pointer,
+            opcode,
+        })
+    }
+
+    fn arg(&self) -> Result<i64, VmError> {
+        match self.memory[self.pointer + 1] {
+            operand @ 0..=7 => Ok(operand),
+            operand => Err(VmError::InvalidOperand {
+                pointer: self.pointer,
+                operand,
+            }),
+        }
+    }
+
+    fn combo_arg(&self) -> Result<i64, VmError> {
+        match self.arg()? {
+            operand @ 0..=3 => Ok(operand),
+            4 => Ok(self.reg_a),
+            5 => Ok(self.reg_b),
+            6 => Ok(self.reg_c),
+            operand => Err(VmError::InvalidOperand {
+                pointer: self.pointer,
+                operand,
+            }),
+        }
+    }
+
+    /// A divided by 2 to the power of the combo operand, for `adv`, `bdv` and `cdv`.
+    fn shifted_a(&self) -> Result<i64, VmError> {
+        let shift = self.combo_arg()?;
+        match u32::try_from(shift) {
+            Ok(bits) if bits < i64::BITS - 1 => Ok(self.reg_a / (1 << bits)),
+            _ => Err(VmError::ShiftOverflow {
+                pointer: self.pointer,
+                shift,
+            }),
+        }
+    }
+
+    /// Executes the instruction under the pointer, returning what it outputs. A failing
+    /// instruction leaves the state as it was.
+    fn advance(&mut self) -> Result<Option<i64>, VmError> {
+        if self.is_halted() {
+            return Ok(None);
+        }
+        let opcode = self.instruction()?;
+        if self.pointer + 1 == self.memory.len() {
+            return Err(VmError::TruncatedInstruction {
+                pointer: self.pointer,
+            });
+        }
+        let mut output = None;
+        match opcode {
+            Opcode::Adv => self.reg_a = self.shifted_a()?,
+            Opcode::Bxl => self.reg_b ^= self.arg()?,
+            Opcode::Bst => self.reg_b = self.combo_arg()? % 8,
+            Opcode::Jnz if self.reg_a != 0 => {
+                self.pointer = self.arg()? as usize;
+                return Ok(None);
+            }
+            Opcode::Jnz => {}
+            Opcode::Bxc => self.reg_b ^= self.reg_c,
+            Opcode::Out => output = Some(self.combo_arg()? % 8),
+            Opcode::Bdv => self.reg_b = self.shifted_a()?,
+            Opcode::Cdv => self.reg_c = self.shifted_a()?,
+        }
+        self.pointer += 2;
+        Ok(output)
+    }
+}
+
+fn parse_state(input: &str) -> Result<State, ParseError> {
+    let mut lines = parse::lines(input);
+    let mut register = |name| {
+        let line = lines.expect(format_args!("register {name}"))?;
+        let value = line.strip_prefix(line.text, &format!("Register {name}: "))?;
+        line.number(value)
+    };
+    let reg_a = register("A")?;
+    let reg_b = register("B")?;
+    let reg_c = register("C")?;
+    let blank = lines.expect("a blank line")?;
+    blank.end(blank.text)?;
+    let line = lines.expect("the program")?;
+    let memory = match line.text.strip_prefix("Program: ") {
+        Some(cells) => cells
+            .split(",")
+            .map(|cell| asm::cell(&line, cell, "a 3-bit number"))
+            .collect::<Result<_, _>>()?,
+        None => asm::assemble_lines(iter::once(line).chain(lines))?,
+    };
+    Ok(State {
+        reg_a,
+        reg_b,
+        reg_c,
+        memory,
+        pointer: 0,
+    })
+}
+
+/// Why the machine stopped without halting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VmError {
+    /// The step limit ran out, most likely in a loop whose A never reaches 0.
+    DidNotHalt { steps: usize },
+    /// A cell read as an opcode is not one of the eight.
+    InvalidOpcode { pointer: usize, opcode: i64 },
+    /// An operand is not a 3-bit number, or is the reserved combo operand 7.
+    InvalidOperand { pointer: usize, operand: i64 },
+    /// The program ends between an opcode and its operand.
+    TruncatedInstruction { pointer: usize },
+    /// A division by 2 to the power of `shift` that an `i64` cannot hold.
+    ShiftOverflow { pointer: usize, shift: i64 },
+}
+
+impl Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VmError::DidNotHalt { steps } => write!(f, "the program did not halt in {steps} steps"),
+            VmError::InvalidOpcode { pointer, opcode } => {
+                write!(f, "invalid opcode {opcode} at {pointer}")
+            }
+            VmError::InvalidOperand { pointer, operand } => {
+                write!(
+                    f,
+                    "invalid operand {operand} for the instruction at {pointer}"
+                )
+            }
+            VmError::TruncatedInstruction { pointer } => {
+                write!(f, "the instruction at {pointer} has no operand")
+            }
+            VmError::ShiftOverflow { pointer, shift } => write!(
+                f,
+                "the instruction at {pointer} divides A by 2 to the power of {shift}, which \
+                 overflows"
+            ),
+        }
+    }
+}
+
+impl Error for VmError {}
+
+/// Runs the program to the end, giving up after `limit` steps.
+fn execute(init_state: State, limit: usize) -> Result<Vec<i64>, VmError> {
+    let mut debugger = Debugger::new(init_state);
+    debugger.limit = limit;
+    debugger.run(|_| ())?;
+    Ok(debugger.into_output())
+}
+
+fn part1(init_state: State, limit: usize) -> Result<String, VmError> {
+    Ok(execute(init_state, limit)?
+        .into_iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+/// Why no value of register A makes the program print itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QuineError {
+    /// The search relies on a single `adv` by a literal 1 to 3 bits, so that each output
+    /// digit depends on a few more bits of A than the ones after it.
+    NoFixedShift,
+    /// Every candidate for A died out before the first digit of the program.
+    NoQuine,
+}
+
+impl Display for QuineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuineError::NoFixedShift => write!(
+                f,
+                "the program does not shift A right by a fixed 1 to 3 bits with a single adv"
+            ),
+            QuineError::NoQuine => write!(f, "no value of A makes the program output itself"),
+        }
+    }
+}
+
+impl Error for QuineError {}
+
+/// The bits `adv` shifts out of A on each pass through the program.
+fn shift(memory: &[i64]) -> Result<u32, QuineError> {
+    let mut shifts = memory
+        .chunks(2)
+        .filter(|instruction| Opcode::new(instruction[0]) == Some(Opcode::Adv))
+        .map(|instruction| instruction.get(1).copied());
+    match (shifts.next(), shifts.next()) {
+        (Some(Some(bits @ 1..=3)), None) => Ok(bits as u32),
+        _ => Err(QuineError::NoFixedShift),
+    }
+}
+
+/// Builds A from its highest bits down: a candidate that makes the program print the last `n`
+/// digits of itself extends to those, `shift` bits longer, that print the last `n + 1`. Every
+/// viable candidate is kept, since the smallest one may lead nowhere; one that faults or does
+/// not halt within `limit` steps prints nothing more, and is dropped.
+fn part2(init_state: State, limit: usize) -> Result<i64, QuineError> {
+    let shift = shift(&init_state.memory)?;
+    let program = &init_state.memory;
+    let mut candidates = BTreeSet::from([0]);
+    for digit in (0..program.len()).rev() {
+        let mut viable = BTreeSet::new();
+        for a in candidates {
+            for a in (0..1 << shift).map(|bits| a << shift | bits) {
+                let state = State {
+                    reg_a: a,
+                    ..init_state.clone()
+                };
+                if execute(state, limit).is_ok_and(|output| output == program[digit..]) {
+                    viable.insert(a);
+                }
+            }
+        }
+        candidates = viable;
+    }
+    candidates.first().copied().ok_or(QuineError::NoQuine)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Input = State;
+    type Answer1 = String;
+    type Answer2 = i64;
+    const PARAMS: &'static [Param] = &[Param {
+        name: "steps",
+        default: 1_000_000,
+        example: 1_000_000,
+    }];
+
+    fn parse(input: &str) -> Result<State> {
+        Ok(parse_state(input)?)
+    }
+
+    fn part1(state: &State) -> Result<String> {
+        Self::part1_with(state, &Self::defaults())
+    }
+
+    fn part2(state: &State) -> Result<i64> {
+        Self::part2_with(state, &Self::defaults())
+    }
+
+    fn part1_with(state: &State, params: &Params) -> Result<String> {
+        Ok(part1(state.clone(), params.get("steps")?)?)
+    }
+
+    fn part2_with(state: &State, params: &Params) -> Result<i64> {
+        Ok(part2(state.clone(), params.get("steps")?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day17.example2.txt");
+    const QUINE: &str = include_str!("../../inputs/day17.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let state = Day17::parse(EXAMPLE).unwrap();
+        assert_eq!(Day17::part1(&state).unwrap(), "4,6,3,5,6,3,5,2,1,0");
+    }
+
+    #[test]
+    fn part2_example() {
+        let state = Day17::parse(QUINE).unwrap();
+        assert_eq!(Day17::part2(&state).unwrap(), 117440);
+    }
+
+    #[test]
+    fn part2_drops_faulting_candidates() {
+        // Faults on `bst 7` when A starts at 0, as the first candidate does.
+        let source = "jnz start\nbst 7\nstart: adv 3\nout A\njnz 0";
+        let memory = asm::assemble(source).unwrap();
+        let state = State::new(0, 0, 0, memory.clone());
+        assert!(execute(state.clone(), 100).is_err());
+        let a = part2(state, 100).unwrap();
+        assert_eq!(a, 481064216);
+        assert_eq!(
+            execute(State::new(a, 0, 0, memory.clone()), 100),
+            Ok(memory)
+        );
+    }
+
+    #[test]
+    fn assemble_with_labels() {
+        let source = "\
+            ; prints A in octal, lowest digit first\n\
+            loop: adv 3\n\
+            \x20     out A ; the digit just shifted down to\n\
+            \x20     jnz loop\n";
+        let memory = asm::assemble(source).unwrap();
+        assert_eq!(memory, Day17::parse(QUINE).unwrap().program());
+        assert_eq!(asm::assemble(&asm::listing(&memory)).unwrap(), memory);
+    }
+
+    #[test]
+    fn disassemble_example() {
+        let state = Day17::parse(QUINE).unwrap();
+        assert_eq!(
+            asm::listing(state.program()),
+            "adv 3     ; 0: A = A >> 3\n\
+             out A     ; 2: out A % 8\n\
+             jnz 0     ; 4: if A != 0 goto 0\n"
+        );
+        assert_eq!(
+            asm::pseudocode(state.program()),
+            "do {\n    A = A >> 3\n    out A % 8\n} while A != 0\n"
+        );
+    }
+
+    #[test]
+    fn step_limit() {
+        let memory = asm::assemble("loop: bxl 1\njnz loop").unwrap();
+        let state = State::new(1, 0, 0, memory);
+        assert_eq!(execute(state, 100), Err(VmError::DidNotHalt { steps: 100 }));
+    }
+
+    #[test]
+    fn breakpoints_and_watchpoints() {
+        let mut debugger = Debugger::new(Day17::parse(QUINE).unwrap());
+        debugger.breakpoints.insert(4);
+        debugger.watches.push(Register::A);
+        let mut trace = Vec::new();
+        let stop = debugger.run(|step| trace.push(step.pointer)).unwrap();
+        assert_eq!(
+            stop,
+            debug::Stop::Watchpoint {
+                register: Register::A,
+                old: 2024,
+                new: 253
+            }
+        );
+        assert_eq!(
+            debugger.run(|step| trace.push(step.pointer)),
+            Ok(debug::Stop::Breakpoint { pointer: 4 })
+        );
+        assert_eq!(debugger.output(), [5]);
+        assert_eq!(trace, [0, 2]);
+    }
+
+    #[test]
+    fn symbolic_quine() {
+        let state = Day17::parse(QUINE).unwrap();
+        let a = symbolic::solve(&state, state.program(), 63, 1000).unwrap();
+        assert_eq!(a, Some(117440));
+    }
+
+    #[test]
+    fn symbolic_smallest_a() {
+        // Two `adv`s a pass, which `part2` cannot search.
+        let source = "loop: adv 1\nbst A\nadv 2\nbxl 3\nbxc 0\nout B\njnz loop";
+        let state = State::new(0, 0, 5, asm::assemble(source).unwrap());
+        let run = |a| execute(State::new(a, 0, 5, state.program().to_vec()), 1000).unwrap();
+        let target = run(12345);
+        let smallest = (0..1 << 16).find(|&a| run(a) == target);
+        assert_eq!(symbolic::solve(&state, &target, 63, 1000), Ok(smallest));
+        assert_eq!(symbolic::solve(&state, &[7, 7, 7], 4, 1000), Ok(None));
+    }
+
+    #[test]
+    fn symbolic_matches_the_machine() {
+        let quine = Day17::parse(QUINE).unwrap().program().to_vec();
+        let smallest_a = "loop: adv 1\nbst A\nadv 2\nbxl 3\nbxc 0\nout B\njnz loop";
+        let programs = [
+            (0, 0, quine),
+            (0, 5, asm::assemble(smallest_a).unwrap()),
+            // Negative B, so that `%` keeps the sign.
+            (-13, 0, vec![2, 5, 5, 5, 0, 1, 3, 0]),
+            // Combo operand 7.
+            (0, 0, vec![0, 1, 5, 7]),
+            // Shifts C by A, which overflows once A is 63.
+            (0, 0, vec![6, 0, 7, 5, 5, 6, 0, 3, 3, 0]),
+            // Ends between an opcode and its operand unless A is nonzero.
+            (0, 0, vec![3, 6, 5, 4, 2]),
+            // Jumps to an operand out of range unless A is 0.
+            (0, 0, vec![3, 9, 5, 4]),
+            // An invalid opcode unless A is nonzero.
+            (0, 0, vec![3, 4, 8, 0, 5, 4]),
+            // Loops forever unless A is 0.
+            (0, 0, vec![1, 1, 3, 0]),
+        ];
+        let limit = 200;
+        for (reg_b, reg_c, memory) in programs {
+            for a in (0..70).chain([12345, 1 << 40]) {
+                let state = State::new(a, reg_b, reg_c, memory.clone());
+                let mut debugger = Debugger::new(state.clone());
+                debugger.limit = limit;
+                let result = debugger.run(|_| ());
+                let output = debugger.output();
+                let accepts = symbolic::accepts(&state, a, output, limit);
+                match result {
+                    Ok(_) => {
+                        assert_eq!(accepts, Ok(true), "{memory:?} with A = {a}");
+                        let longer = [output, &[0]].concat();
+                        assert_eq!(
+                            symbolic::accepts(&state, a, &longer, limit),
+                            Ok(false),
+                            "{memory:?} with A = {a}"
+                        );
+                    }
+                    Err(VmError::DidNotHalt { steps }) => {
+                        assert_eq!(accepts, Err(VmError::DidNotHalt { steps }));
+                    }
+                    Err(_) => assert_eq!(accepts, Ok(false), "{memory:?} with A = {a}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn vm_errors() {
+        let run = |reg_a, memory: &[i64]| execute(State::new(reg_a, 0, 0, memory.to_vec()), 100);
+        assert_eq!(
+            run(0, &[9, 0]),
+            Err(VmError::InvalidOpcode {
+                pointer: 0,
+                opcode: 9
+            })
+        );
+        assert_eq!(
+            run(0, &[5, 7]),
+            Err(VmError::InvalidOperand {
+                pointer: 0,
+                operand: 7
+            })
+        );
+        assert_eq!(
+            run(0, &[1, 3, 2]),
+            Err(VmError::TruncatedInstruction { pointer: 2 })
+        );
+        assert_eq!(
+            run(100, &[0, 4]),
+            Err(VmError::ShiftOverflow {
+                pointer: 0,
+                shift: 100
+            })
+        );
+    }
+}
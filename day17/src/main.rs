@@ -1,183 +1,131 @@
-use std::io::stdin;
+use anyhow::{bail, Context, Result};
+use aoc_common::{InputArgs, Solution};
+use day17::{
+    asm::{self, Register},
+    debug::{Debugger, Stop},
+    symbolic, Day17,
+};
+use std::{env, process::ExitCode};
 
-#[derive(Debug, Clone)]
-struct State {
-    reg_a: i64,
-    reg_b: i64,
-    reg_c: i64,
-    memory: Vec<i64>,
-    pointer: usize,
+/// Prints the program as the numbers that follow `Program: `.
+fn assemble(args: Vec<String>) -> Result<()> {
+    let state = Day17::parse(&InputArgs::parse(args)?.read(Day17::DAY)?)?;
+    let cells = state.program().iter().map(|cell| cell.to_string());
+    println!("{}", cells.collect::<Vec<_>>().join(","));
+    Ok(())
 }
 
-impl State {
-    fn is_halted(&self) -> bool {
-        self.pointer >= self.memory.len()
-    }
-
-    fn instruction(&self) -> i64 {
-        self.memory[self.pointer]
-    }
-
-    fn arg(&self) -> i64 {
-        self.memory[self.pointer + 1]
-    }
+/// Prints the program as an annotated listing, then as structured pseudo-code.
+fn disasm(args: Vec<String>) -> Result<()> {
+    let state = Day17::parse(&InputArgs::parse(args)?.read(Day17::DAY)?)?;
+    print!("{}", asm::listing(state.program()));
+    println!();
+    print!("{}", asm::pseudocode(state.program()));
+    Ok(())
+}
 
-    fn combo_arg(&self) -> i64 {
-        match self.memory[self.pointer + 1] {
-            0 => 0,
-            1 => 1,
-            2 => 2,
-            3 => 3,
-            4 => self.reg_a,
-            5 => self.reg_b,
-            6 => self.reg_c,
-            _ => panic!(),
+/// Runs the program under the debugger, taking
+/// `[PATH] [--example] [--trace] [--steps N] [--break ADDRESS]... [--watch A|B|C]...`.
+/// Breakpoints and watchpoints print the registers and carry on.
+fn debug(args: Vec<String>) -> Result<()> {
+    let mut input_args = Vec::new();
+    let mut trace = false;
+    let mut limit = None;
+    let mut breakpoints = Vec::<usize>::new();
+    let mut watches = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().with_context(|| format!("{arg} needs a value"));
+        match arg.as_str() {
+            "--trace" => trace = true,
+            "--steps" => limit = Some(value()?.parse().context("--steps must be a number")?),
+            "--break" => breakpoints.push(value()?.parse().context("--break needs an address")?),
+            "--watch" => watches.push(match value()?.as_str() {
+                "A" => Register::A,
+                "B" => Register::B,
+                "C" => Register::C,
+                other => bail!("--watch needs A, B or C, got {other:?}"),
+            }),
+            _ => input_args.push(arg),
         }
     }
-
-    fn advance(self) -> (State, Option<i64>) {
-        let mut output = None;
-        let state = if self.is_halted() {
-            self
-        } else {
-            match self.instruction() {
-                0 => State {
-                    reg_a: self.reg_a / (1 << self.combo_arg()),
-                    pointer: self.pointer + 2,
-                    ..self
-                },
-                1 => State {
-                    reg_b: self.reg_b ^ self.arg(),
-                    pointer: self.pointer + 2,
-                    ..self
-                },
-                2 => State {
-                    reg_b: self.combo_arg() % 8,
-                    pointer: self.pointer + 2,
-                    ..self
-                },
-                3 => State {
-                    pointer: if self.reg_a == 0 {
-                        self.pointer + 2
-                    } else {
-                        self.arg() as usize
-                    },
-                    ..self
-                },
-                4 => State {
-                    reg_b: self.reg_b ^ self.reg_c,
-                    pointer: self.pointer + 2,
-                    ..self
-                },
-                5 => {
-                    output = Some(self.combo_arg() % 8);
-                    State {
-                        pointer: self.pointer + 2,
-                        ..self
-                    }
-                }
-                6 => State {
-                    reg_b: self.reg_a / (1 << self.combo_arg()),
-                    pointer: self.pointer + 2,
-                    ..self
-                },
-                7 => State {
-                    reg_c: self.reg_a / (1 << self.combo_arg()),
-                    pointer: self.pointer + 2,
-                    ..self
-                },
-                _ => panic!(),
+    let state = Day17::parse(&InputArgs::parse(input_args)?.read(Day17::DAY)?)?;
+    let mut debugger = Debugger::new(state);
+    debugger.limit = limit.unwrap_or(Day17::defaults().get("steps")?);
+    debugger.breakpoints.extend(breakpoints);
+    debugger.watches = watches;
+    loop {
+        let stop = debugger.run(|step| {
+            if trace {
+                println!("{step}");
             }
-        };
-        (state, output)
-    }
-}
-
-fn parse_state() -> State {
-    let mut line = String::new();
-    stdin().read_line(&mut line).unwrap();
-    let reg_a = line
-        .trim()
-        .strip_prefix("Register A: ")
-        .unwrap()
-        .parse()
-        .unwrap();
-    line.clear();
-    stdin().read_line(&mut line).unwrap();
-    let reg_b = line
-        .trim()
-        .strip_prefix("Register B: ")
-        .unwrap()
-        .parse()
-        .unwrap();
-    line.clear();
-    stdin().read_line(&mut line).unwrap();
-    let reg_c = line
-        .trim()
-        .strip_prefix("Register C: ")
-        .unwrap()
-        .parse()
-        .unwrap();
-    stdin().read_line(&mut line).unwrap();
-    line.clear();
-    stdin().read_line(&mut line).unwrap();
-    let memory = line
-        .trim()
-        .strip_prefix("Program: ")
-        .unwrap()
-        .split(",")
-        .map(|cell| cell.parse().unwrap())
-        .collect();
-    State {
-        reg_a,
-        reg_b,
-        reg_c,
-        memory,
-        pointer: 0,
-    }
-}
-
-fn execute(init_state: State) -> Vec<i64> {
-    let mut state = init_state;
-    let mut outputs = Vec::new();
-    while !state.is_halted() {
-        let (next_state, output) = state.advance();
-        state = next_state;
-        if let Some(output) = output {
-            outputs.push(output);
+        })?;
+        if stop == Stop::Halted {
+            break;
         }
+        let state = debugger.state();
+        let [a, b, c] = [Register::A, Register::B, Register::C].map(|r| state.register(r));
+        println!("{stop} after {} steps: A={a} B={b} C={c}", debugger.steps());
     }
-    outputs
+    let output = debugger.output().iter().map(|n| n.to_string());
+    println!(
+        "halted after {} steps, output {}",
+        debugger.steps(),
+        output.collect::<Vec<_>>().join(",")
+    );
+    Ok(())
 }
 
-fn part1(init_state: State) -> String {
-    execute(init_state)
-        .into_iter()
-        .map(|n| n.to_string())
-        .collect::<Vec<_>>()
-        .join(",")
-}
-
-fn part2(init_state: State) -> i64 {
-    let mut result = 0;
-    for _ in init_state.memory.iter().rev() {
-        for candidate in 0.. {
-            println!("Trying {candidate}");
-            let mut state = init_state.clone();
-            state.reg_a = (result << 3) + candidate;
-            let output = execute(state);
-            println!("Output: {output:?}");
-            if init_state.memory.ends_with(&output[..]) {
-                result = (result << 3) + candidate;
-                break;
+/// Finds the smallest A that makes the program output a sequence and halt, taking
+/// `[PATH] [--example] [--output 1,2,3] [--bits N] [--steps N]`. The sequence defaults to the
+/// program itself, and A to below 2^63.
+fn solve(args: Vec<String>) -> Result<()> {
+    let mut input_args = Vec::new();
+    let mut target = None;
+    let mut bits = i64::BITS - 1;
+    let mut limit = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().with_context(|| format!("{arg} needs a value"));
+        match arg.as_str() {
+            "--output" => {
+                let digits: Result<Vec<i64>, _> = value()?.split(',').map(str::parse).collect();
+                target = Some(digits.context("--output must be numbers separated by commas")?);
             }
+            "--bits" => bits = value()?.parse().context("--bits must be a number")?,
+            "--steps" => limit = Some(value()?.parse().context("--steps must be a number")?),
+            _ => input_args.push(arg),
         }
     }
-    result
+    let state = Day17::parse(&InputArgs::parse(input_args)?.read(Day17::DAY)?)?;
+    let target = target.unwrap_or_else(|| state.program().to_vec());
+    let limit = limit.unwrap_or(Day17::defaults().get("steps")?);
+    match symbolic::solve(&state, &target, bits, limit)? {
+        Some(a) => println!("{a}"),
+        None => bail!("no value of A below 2^{bits} makes the program output that"),
+    }
+    Ok(())
 }
 
-fn main() {
-    let state = parse_state();
-    println!("Part 1: {}", part1(state.clone()));
-    println!("Part 2: {}", part2(state));
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    match args.first().map(String::as_str) {
+        Some("asm") => {
+            args.remove(0);
+            aoc_common::exit_code(assemble(args))
+        }
+        Some("disasm") => {
+            args.remove(0);
+            aoc_common::exit_code(disasm(args))
+        }
+        Some("debug") => {
+            args.remove(0);
+            aoc_common::exit_code(debug(args))
+        }
+        Some("solve") => {
+            args.remove(0);
+            aoc_common::exit_code(solve(args))
+        }
+        _ => aoc_common::run::<Day17>(),
+    }
 }
@@ -0,0 +1,347 @@
+//! A small CDCL SAT solver: clauses over numbered variables, two watched literals per clause,
+//! conflict analysis to the first unique implication point, and solving under assumptions so
+//! that one set of clauses can answer several related questions.
+
+use std::{mem, ops::Not};
+
+/// A variable or its negation. Variable 0 is the constant true.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Lit(u32);
+
+impl Lit {
+    pub const TRUE: Lit = Lit(0);
+    pub const FALSE: Lit = Lit(1);
+
+    pub fn var(self) -> usize {
+        (self.0 >> 1) as usize
+    }
+
+    pub fn is_negated(self) -> bool {
+        self.0 & 1 == 1
+    }
+
+    pub fn is_constant(self) -> bool {
+        self.var() == 0
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Not for Lit {
+    type Output = Lit;
+    fn not(self) -> Lit {
+        Lit(self.0 ^ 1)
+    }
+}
+
+/// A satisfying assignment, by variable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Model(Vec<bool>);
+
+impl Model {
+    pub fn value(&self, lit: Lit) -> bool {
+        self.0[lit.var()] != lit.is_negated()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Solver {
+    clauses: Vec<Vec<Lit>>,
+    /// For each literal, the clauses watching it, to revisit when it becomes false.
+    watches: Vec<Vec<usize>>,
+    values: Vec<Option<bool>>,
+    levels: Vec<usize>,
+    /// The clause that forced each variable, or `None` for decisions.
+    reasons: Vec<Option<usize>>,
+    trail: Vec<Lit>,
+    /// Where each decision level starts on the trail.
+    level_starts: Vec<usize>,
+    propagated: usize,
+    activity: Vec<f64>,
+    bump: f64,
+    /// The value each variable last had, tried first when deciding it again.
+    phases: Vec<bool>,
+    /// Variables to decide before any other, such as the inputs of a circuit, which decide the
+    /// rest by propagation.
+    preferred: Vec<usize>,
+    /// Whether the clauses are unsatisfiable whatever the assumptions.
+    unsat: bool,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        let mut solver = Solver {
+            clauses: Vec::new(),
+            watches: Vec::new(),
+            values: Vec::new(),
+            levels: Vec::new(),
+            reasons: Vec::new(),
+            trail: Vec::new(),
+            level_starts: Vec::new(),
+            propagated: 0,
+            activity: Vec::new(),
+            bump: 1.0,
+            phases: Vec::new(),
+            preferred: Vec::new(),
+            unsat: false,
+        };
+        let constant = solver.new_var();
+        solver.add_clause(&[constant]);
+        solver
+    }
+
+    /// A fresh variable, as its positive literal.
+    pub fn new_var(&mut self) -> Lit {
+        let var = self.values.len();
+        self.values.push(None);
+        self.levels.push(0);
+        self.reasons.push(None);
+        self.activity.push(0.0);
+        self.phases.push(false);
+        self.watches.extend([Vec::new(), Vec::new()]);
+        Lit(var as u32 * 2)
+    }
+
+    pub fn prefer(&mut self, lit: Lit) {
+        self.preferred.push(lit.var());
+    }
+
+    pub fn add_clause(&mut self, lits: &[Lit]) {
+        let mut clause = Vec::with_capacity(lits.len());
+        for &lit in lits {
+            match self.value(lit) {
+                Some(true) => return,
+                Some(false) => {}
+                None if clause.contains(&!lit) => return,
+                None if !clause.contains(&lit) => clause.push(lit),
+                None => {}
+            }
+        }
+        match clause[..] {
+            [] => self.unsat = true,
+            [lit] => {
+                self.assign(lit, None);
+                if self.propagate().is_some() {
+                    self.unsat = true;
+                }
+            }
+            _ => {
+                self.attach(clause);
+            }
+        }
+    }
+
+    /// A model satisfying every clause with every assumption true, if there is one.
+    pub fn solve(&mut self, assumptions: &[Lit]) -> Option<Model> {
+        let mut conflicts = 0;
+        let mut restart = 100;
+        let model = loop {
+            if self.unsat {
+                break None;
+            }
+            if let Some(conflict) = self.propagate() {
+                if self.level() == 0 {
+                    self.unsat = true;
+                    break None;
+                }
+                conflicts += 1;
+                let (learnt, level) = self.analyze(conflict);
+                self.backtrack(level);
+                let lit = learnt[0];
+                if learnt.len() == 1 {
+                    self.assign(lit, None);
+                } else {
+                    let clause = self.attach(learnt);
+                    self.assign(lit, Some(clause));
+                }
+                self.bump *= 1.05;
+                continue;
+            }
+            if conflicts >= restart {
+                conflicts = 0;
+                restart += restart / 2;
+                self.backtrack(0);
+                continue;
+            }
+            // Assumptions take the first decision levels, one each.
+            if let Some(&assumption) = assumptions.get(self.level()) {
+                match self.value(assumption) {
+                    Some(false) => break None,
+                    Some(true) => self.level_starts.push(self.trail.len()),
+                    None => {
+                        self.level_starts.push(self.trail.len());
+                        self.assign(assumption, None);
+                    }
+                }
+                continue;
+            }
+            let Some(var) = self.pick() else {
+                break Some(Model(self.values.iter().map(|v| v.unwrap()).collect()));
+            };
+            self.level_starts.push(self.trail.len());
+            self.assign(Lit(var as u32 * 2 + !self.phases[var] as u32), None);
+        };
+        self.backtrack(0);
+        model
+    }
+
+    fn value(&self, lit: Lit) -> Option<bool> {
+        self.values[lit.var()].map(|value| value != lit.is_negated())
+    }
+
+    fn level(&self) -> usize {
+        self.level_starts.len()
+    }
+
+    fn attach(&mut self, clause: Vec<Lit>) -> usize {
+        let index = self.clauses.len();
+        self.watches[clause[0].index()].push(index);
+        self.watches[clause[1].index()].push(index);
+        self.clauses.push(clause);
+        index
+    }
+
+    fn assign(&mut self, lit: Lit, reason: Option<usize>) {
+        let var = lit.var();
+        self.values[var] = Some(!lit.is_negated());
+        self.levels[var] = self.level();
+        self.reasons[var] = reason;
+        self.trail.push(lit);
+    }
+
+    /// Assigns everything the clauses force, returning a clause that became false, if any.
+    fn propagate(&mut self) -> Option<usize> {
+        while let Some(&lit) = self.trail.get(self.propagated) {
+            self.propagated += 1;
+            let falsified = !lit;
+            let watching = mem::take(&mut self.watches[falsified.index()]);
+            let mut kept = Vec::with_capacity(watching.len());
+            let mut conflict = None;
+            for (position, &index) in watching.iter().enumerate() {
+                if conflict.is_some() {
+                    kept.extend_from_slice(&watching[position..]);
+                    break;
+                }
+                let clause = &mut self.clauses[index];
+                if clause[0] == falsified {
+                    clause.swap(0, 1);
+                }
+                let first = clause[0];
+                if self.values[first.var()].map(|value| value != first.is_negated()) == Some(true) {
+                    kept.push(index);
+                    continue;
+                }
+                let replacement = (2..clause.len()).find(|&k| {
+                    let lit = clause[k];
+                    self.values[lit.var()].map(|value| value != lit.is_negated()) != Some(false)
+                });
+                if let Some(k) = replacement {
+                    clause.swap(1, k);
+                    let watch = clause[1];
+                    self.watches[watch.index()].push(index);
+                    continue;
+                }
+                kept.push(index);
+                match self.value(first) {
+                    Some(false) => conflict = Some(index),
+                    _ => self.assign(first, Some(index)),
+                }
+            }
+            self.watches[falsified.index()].extend(kept);
+            if conflict.is_some() {
+                return conflict;
+            }
+        }
+        None
+    }
+
+    /// Learns a clause from a conflict, returning it with the literal it asserts first and the
+    /// level to jump back to, at which it becomes unit.
+    fn analyze(&mut self, conflict: usize) -> (Vec<Lit>, usize) {
+        let mut seen = vec![false; self.values.len()];
+        let mut learnt = vec![Lit::TRUE];
+        let mut pending = 0;
+        let mut clause = conflict;
+        let mut position = self.trail.len();
+        let asserting = loop {
+            // A reason clause starts with the literal it forced, which is already resolved.
+            let skip = (clause != conflict) as usize;
+            for k in skip..self.clauses[clause].len() {
+                let lit = self.clauses[clause][k];
+                let var = lit.var();
+                if seen[var] || self.levels[var] == 0 {
+                    continue;
+                }
+                seen[var] = true;
+                self.activity[var] += self.bump;
+                if self.levels[var] == self.level() {
+                    pending += 1;
+                } else {
+                    learnt.push(lit);
+                }
+            }
+            loop {
+                position -= 1;
+                if seen[self.trail[position].var()] {
+                    break;
+                }
+            }
+            let lit = self.trail[position];
+            seen[lit.var()] = false;
+            pending -= 1;
+            if pending == 0 {
+                break lit;
+            }
+            clause = self.reasons[lit.var()].unwrap();
+        };
+        learnt[0] = !asserting;
+        let mut level = 0;
+        if let Some(k) = (1..learnt.len()).max_by_key(|&k| self.levels[learnt[k].var()]) {
+            learnt.swap(1, k);
+            level = self.levels[learnt[1].var()];
+        }
+        if self.activity[asserting.var()] > 1e100 {
+            self.activity
+                .iter_mut()
+                .for_each(|activity| *activity *= 1e-100);
+            self.bump *= 1e-100;
+        }
+        (learnt, level)
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.level() <= level {
+            return;
+        }
+        let start = self.level_starts[level];
+        for lit in self.trail.drain(start..) {
+            let var = lit.var();
+            self.phases[var] = !lit.is_negated();
+            self.values[var] = None;
+            self.reasons[var] = None;
+        }
+        self.level_starts.truncate(level);
+        self.propagated = start;
+    }
+
+    /// The unassigned variable to decide next: the most active preferred one, or else any.
+    fn pick(&self) -> Option<usize> {
+        let unassigned = |&var: &usize| self.values[var].is_none();
+        let preferred = self.preferred.iter().copied().filter(unassigned);
+        preferred
+            .max_by(|&a, &b| {
+                self.activity[a]
+                    .total_cmp(&self.activity[b])
+                    .then(b.cmp(&a))
+            })
+            .or_else(|| (0..self.values.len()).find(unassigned))
+    }
+}
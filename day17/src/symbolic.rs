@@ -0,0 +1,359 @@
+//! Running a program on an unknown register A. Every register holds 64 propositions about A's
+//! bits, a `jnz` that depends on A forks the run, and each run that halts having output the
+//! target gives the [`Solver`] the constraints under which it happens. Unlike `part2`, this
+//! makes no assumption about the shape of the program.
+
+use crate::{
+    asm::{self, Decoded, Instruction, Opcode, Operand, Register},
+    sat::{Lit, Solver},
+    State, VmError,
+};
+use std::{array, collections::HashMap};
+
+/// A register's value in two's complement, lowest bit first.
+type Word = [Lit; 64];
+
+fn constant(value: i64) -> Word {
+    array::from_fn(|bit| {
+        if value >> bit & 1 == 1 {
+            Lit::TRUE
+        } else {
+            Lit::FALSE
+        }
+    })
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+enum Gate {
+    And,
+    Xor,
+}
+
+/// Turns gates into solver variables, folding constants and sharing gates built twice.
+struct Circuit {
+    solver: Solver,
+    gates: HashMap<(Gate, Lit, Lit), Lit>,
+}
+
+impl Circuit {
+    fn new() -> Self {
+        Circuit {
+            solver: Solver::new(),
+            gates: HashMap::new(),
+        }
+    }
+
+    fn gate(&mut self, gate: Gate, a: Lit, b: Lit) -> Lit {
+        if let Some(&out) = self.gates.get(&(gate, a, b)) {
+            return out;
+        }
+        let out = self.solver.new_var();
+        let clauses = match gate {
+            Gate::And => vec![vec![!out, a], vec![!out, b], vec![out, !a, !b]],
+            Gate::Xor => vec![
+                vec![!out, a, b],
+                vec![!out, !a, !b],
+                vec![out, !a, b],
+                vec![out, a, !b],
+            ],
+        };
+        for clause in clauses {
+            self.solver.add_clause(&clause);
+        }
+        self.gates.insert((gate, a, b), out);
+        out
+    }
+
+    fn and(&mut self, a: Lit, b: Lit) -> Lit {
+        let (a, b) = (a.min(b), a.max(b));
+        match a {
+            Lit::TRUE => b,
+            Lit::FALSE => Lit::FALSE,
+            _ if a == b => a,
+            _ if a == !b => Lit::FALSE,
+            _ => self.gate(Gate::And, a, b),
+        }
+    }
+
+    fn or(&mut self, a: Lit, b: Lit) -> Lit {
+        !self.and(!a, !b)
+    }
+
+    fn xor(&mut self, a: Lit, b: Lit) -> Lit {
+        // Only positive inputs, so that `a ^ !b` shares the gate for `a ^ b`.
+        let negated = a.is_negated() != b.is_negated();
+        let (a, b) = (a.min(!a), b.min(!b));
+        let (a, b) = (a.min(b), a.max(b));
+        let out = match a {
+            Lit::TRUE => !b,
+            _ if a == b => Lit::FALSE,
+            _ => self.gate(Gate::Xor, a, b),
+        };
+        if negated {
+            !out
+        } else {
+            out
+        }
+    }
+
+    /// `then` if `condition` holds, else `otherwise`.
+    fn select(&mut self, condition: Lit, then: Lit, otherwise: Lit) -> Lit {
+        if then == otherwise {
+            return then;
+        }
+        let then = self.and(condition, then);
+        let otherwise = self.and(!condition, otherwise);
+        self.or(then, otherwise)
+    }
+
+    fn any(&mut self, bits: &[Lit]) -> Lit {
+        bits.iter().fold(Lit::FALSE, |any, &bit| self.or(any, bit))
+    }
+
+    fn all(&mut self, bits: &[Lit]) -> Lit {
+        bits.iter().fold(Lit::TRUE, |all, &bit| self.and(all, bit))
+    }
+
+    fn xor_words(&mut self, a: &Word, b: &Word) -> Word {
+        array::from_fn(|bit| self.xor(a[bit], b[bit]))
+    }
+
+    /// `word % 8`, which keeps the sign as Rust's `%` does.
+    fn rem8(&mut self, word: &Word) -> Word {
+        let inexact = self.any(&word[..3]);
+        let negative = self.and(word[63], inexact);
+        array::from_fn(|bit| if bit < 3 { word[bit] } else { negative })
+    }
+
+    /// `a / 2^shift` for a non-negative `a`, with whether the shift overflows as it does in
+    /// `State::shifted_a`.
+    fn shift(&mut self, a: &Word, shift: &Word) -> (Word, Lit) {
+        let high = self.any(&shift[6..]);
+        let all_ones = self.all(&shift[..6]);
+        let overflow = self.or(high, all_ones);
+        let mut word = *a;
+        for (stage, &enabled) in shift[..6].iter().enumerate() {
+            word = array::from_fn(|bit| {
+                let shifted = word.get(bit + (1 << stage)).copied().unwrap_or(Lit::FALSE);
+                self.select(enabled, shifted, word[bit])
+            });
+        }
+        (word, overflow)
+    }
+}
+
+/// One path through the program, with what it requires of A so far.
+#[derive(Clone, Debug)]
+struct Run {
+    registers: [Word; 3],
+    pointer: usize,
+    steps: usize,
+    /// How many digits of the target it has output.
+    output: usize,
+    constraints: Vec<Lit>,
+}
+
+impl Run {
+    fn register(&mut self, register: Register) -> &mut Word {
+        &mut self.registers[register as usize]
+    }
+
+    /// Adds a constraint, returning whether the run can still happen.
+    fn require(&mut self, lit: Lit) -> bool {
+        match lit {
+            Lit::TRUE => true,
+            Lit::FALSE => false,
+            _ => {
+                self.constraints.push(lit);
+                true
+            }
+        }
+    }
+}
+
+impl Circuit {
+    /// Executes the instruction under the run's pointer as `State::advance` would, returning
+    /// the runs it leads to: none if it faults or outputs something other than the next digit
+    /// of `target`, and two if it is a `jnz` that depends on A.
+    fn advance(&mut self, mut run: Run, memory: &[i64], target: &[i64]) -> Vec<Run> {
+        let Decoded::Instruction(Instruction { opcode, operand }) =
+            asm::decode(memory, run.pointer)
+        else {
+            return Vec::new();
+        };
+        let literal = match operand {
+            Operand::Literal(value @ 0..=7) => Some(value),
+            _ => None,
+        };
+        let combo = match operand {
+            Operand::Register(register) => Some(run.registers[register as usize]),
+            _ => literal.map(constant),
+        };
+        run.steps += 1;
+        let [a, b, c] = run.registers;
+        match opcode {
+            Opcode::Adv | Opcode::Bdv | Opcode::Cdv => {
+                let Some(shift) = combo else {
+                    return Vec::new();
+                };
+                let (value, overflow) = self.shift(&a, &shift);
+                if !run.require(!overflow) {
+                    return Vec::new();
+                }
+                let register = match opcode {
+                    Opcode::Adv => Register::A,
+                    Opcode::Bdv => Register::B,
+                    _ => Register::C,
+                };
+                *run.register(register) = value;
+            }
+            Opcode::Bxl => {
+                let Some(value) = literal else {
+                    return Vec::new();
+                };
+                *run.register(Register::B) = self.xor_words(&b, &constant(value));
+            }
+            Opcode::Bst => {
+                let Some(value) = combo else {
+                    return Vec::new();
+                };
+                *run.register(Register::B) = self.rem8(&value);
+            }
+            Opcode::Jnz => {
+                let nonzero = self.any(&a);
+                let mut runs = Vec::new();
+                if let Some(address) = literal {
+                    let mut jumped = run.clone();
+                    jumped.pointer = address as usize;
+                    if jumped.require(nonzero) {
+                        runs.push(jumped);
+                    }
+                }
+                // Not jumping never reads the operand, so it cannot fault.
+                run.pointer += 2;
+                if run.require(!nonzero) {
+                    runs.push(run);
+                }
+                return runs;
+            }
+            Opcode::Bxc => *run.register(Register::B) = self.xor_words(&b, &c),
+            Opcode::Out => {
+                let (Some(value), Some(&digit)) = (combo, target.get(run.output)) else {
+                    return Vec::new();
+                };
+                let value = self.rem8(&value);
+                let digit = constant(digit);
+                let matches = (0..64).all(|bit| {
+                    run.require(if digit[bit] == Lit::TRUE {
+                        value[bit]
+                    } else {
+                        !value[bit]
+                    })
+                });
+                if !matches {
+                    return Vec::new();
+                }
+                run.output += 1;
+            }
+        }
+        run.pointer += 2;
+        vec![run]
+    }
+}
+
+/// The constraints of each run of the program from register A `a`, with the B and C of
+/// `state`, that halts having output exactly `target`.
+fn halting_runs(
+    circuit: &mut Circuit,
+    a: Word,
+    state: &State,
+    target: &[i64],
+    limit: usize,
+) -> Result<Vec<Vec<Lit>>, VmError> {
+    let memory = state.program();
+    let mut runs = vec![Run {
+        registers: [
+            a,
+            constant(state.register(Register::B)),
+            constant(state.register(Register::C)),
+        ],
+        pointer: 0,
+        steps: 0,
+        output: 0,
+        constraints: Vec::new(),
+    }];
+    let mut halted = Vec::new();
+    while let Some(run) = runs.pop() {
+        if run.pointer >= memory.len() {
+            if run.output == target.len() {
+                halted.push(run.constraints);
+            }
+            continue;
+        }
+        if run.steps == limit {
+            return Err(VmError::DidNotHalt { steps: limit });
+        }
+        runs.extend(circuit.advance(run, memory, target));
+    }
+    Ok(halted)
+}
+
+/// The smallest A below `2^bits` for which the program, with the B and C of `state`, outputs
+/// exactly `target` and halts, or `None` if there is none. A run still going after `limit`
+/// instructions fails with [`VmError::DidNotHalt`], as it might yet output `target`; runs that
+/// fault output nothing more and are dropped.
+pub fn solve(
+    state: &State,
+    target: &[i64],
+    bits: u32,
+    limit: usize,
+) -> Result<Option<i64>, VmError> {
+    let bits = bits.min(i64::BITS - 1) as usize;
+    let mut circuit = Circuit::new();
+    // Highest bit first, as the solver decides ties in the order variables were made.
+    let mut inputs = [Lit::FALSE; 64];
+    for bit in (0..bits).rev() {
+        inputs[bit] = circuit.solver.new_var();
+        circuit.solver.prefer(inputs[bit]);
+    }
+    // Each run that halts with the whole target gets a variable that implies its constraints,
+    // and one of those must hold.
+    let mut halts = Vec::new();
+    for constraints in halting_runs(&mut circuit, inputs, state, target, limit)? {
+        let halt = circuit.solver.new_var();
+        for constraint in constraints {
+            circuit.solver.add_clause(&[!halt, constraint]);
+        }
+        halts.push(halt);
+    }
+    circuit.solver.add_clause(&halts);
+    let solver = &mut circuit.solver;
+    let Some(mut model) = solver.solve(&[]) else {
+        return Ok(None);
+    };
+    // Clear each bit from the top that some solution allows to be clear.
+    let mut fixed = Vec::new();
+    for bit in (0..bits).rev() {
+        let input = inputs[bit];
+        if model.value(input) {
+            fixed.push(!input);
+            match solver.solve(&fixed) {
+                Some(smaller) => model = smaller,
+                None => *fixed.last_mut().unwrap() = input,
+            }
+        } else {
+            fixed.push(!input);
+        }
+    }
+    Ok(Some((0..bits).fold(0, |a, bit| {
+        a | (model.value(inputs[bit]) as i64) << bit
+    })))
+}
+
+/// Whether the program, with register A `a` and the B and C of `state`, outputs exactly `target`
+/// and halts, found by the same circuit as [`solve`] with every bit of A fixed. It fails as
+/// `solve` does, so that the circuit can be checked against [`State`] on concrete values.
+pub fn accepts(state: &State, a: i64, target: &[i64], limit: usize) -> Result<bool, VmError> {
+    let halted = halting_runs(&mut Circuit::new(), constant(a), state, target, limit)?;
+    Ok(!halted.is_empty())
+}
@@ -0,0 +1,167 @@
+use anyhow::{Context, Result};
+use aoc_common::{
+    parse,
+    render::{Frame, Recorder, BLACK, BLUE, RED, YELLOW},
+    search, Param, Params, ParseError, Pos, Solution,
+};
+use std::collections::HashSet;
+
+fn distance(src: Pos, dst: Pos, r_max: i32, c_max: i32, barriers: HashSet<Pos>) -> Option<i32> {
+    shortest_path(src, dst, r_max, c_max, &barriers).map(|(dist, _)| dist)
+}
+
+fn shortest_path(
+    src: Pos,
+    dst: Pos,
+    r_max: i32,
+    c_max: i32,
+    barriers: &HashSet<Pos>,
+) -> Option<(i32, Vec<Pos>)> {
+    search::astar(
+        src,
+        |&pos| {
+            pos.neighbors4()
+                .filter(|&pos| pos.r >= 0 && pos.r < r_max && pos.c >= 0 && pos.c < c_max)
+                .filter(|pos| !barriers.contains(pos))
+                .map(|pos| (pos, 1))
+                .collect::<Vec<_>>()
+        },
+        |&pos| pos.manhattan(dst) as i32,
+        |&pos| pos == dst,
+    )
+}
+
+fn part1(input: &[Pos], r_max: i32, c_max: i32, num_barriers: usize) -> Result<i32> {
+    distance(
+        Pos::ORIGIN,
+        Pos::new(r_max - 1, c_max - 1),
+        r_max,
+        c_max,
+        input.iter().take(num_barriers).copied().collect(),
+    )
+    .with_context(|| format!("the first {num_barriers} bytes cut off the exit"))
+}
+
+fn part2(input: &[Pos], r_max: i32, c_max: i32) -> Result<String> {
+    let iota = (1..=input.len()).collect::<Vec<_>>();
+    let num_corrupt = iota.partition_point(|&num_corrupt| {
+        distance(
+            Pos::ORIGIN,
+            Pos::new(r_max - 1, c_max - 1),
+            r_max,
+            c_max,
+            input.iter().take(num_corrupt).copied().collect(),
+        )
+        .is_some()
+    });
+    let pos = input
+        .get(num_corrupt)
+        .context("no byte cuts off the exit")?;
+    Ok(format!("{},{}", pos.c, pos.r))
+}
+
+/// A frame per falling byte with a shortest path in blue, until the byte that cuts off the exit,
+/// which is drawn in yellow.
+fn render(input: &[Pos], size: i32, recorder: &mut Recorder) -> Result<()> {
+    let (src, dst) = (Pos::ORIGIN, Pos::new(size - 1, size - 1));
+    let mut frame = Frame::new(size as usize, size as usize, BLACK);
+    let mut barriers = HashSet::new();
+    let mut path = shortest_path(src, dst, size, size, &barriers).map(|(_, path)| path);
+    let with_path = |frame: &Frame, path: &[Pos]| {
+        let mut frame = frame.clone();
+        frame.paint_all(path.iter().copied(), BLUE);
+        frame
+    };
+    for &byte in input {
+        let Some(current) = &path else { break };
+        recorder.record(|| with_path(&frame, current))?;
+        frame.paint(byte, RED);
+        barriers.insert(byte);
+        if current.contains(&byte) {
+            path = shortest_path(src, dst, size, size, &barriers).map(|(_, path)| path);
+            if path.is_none() {
+                frame.paint(byte, YELLOW);
+                return recorder.save(&frame);
+            }
+        }
+    }
+    recorder.finish(|| with_path(&frame, path.as_deref().unwrap_or_default()))
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Input = Vec<Pos>;
+    type Answer1 = i32;
+    type Answer2 = String;
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "size",
+            default: 71,
+            example: 7,
+        },
+        Param {
+            name: "bytes",
+            default: 1024,
+            example: 12,
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Vec<Pos>> {
+        Ok(parse::lines(input)
+            .map(|line| {
+                let (c, r) = line.split_once(line.text, ",")?;
+                Ok(Pos::new(line.number(r)?, line.number(c)?))
+            })
+            .collect::<Result<_, ParseError>>()?)
+    }
+
+    fn part1(input: &Vec<Pos>) -> Result<i32> {
+        Self::part1_with(input, &Self::defaults())
+    }
+
+    fn part2(input: &Vec<Pos>) -> Result<String> {
+        Self::part2_with(input, &Self::defaults())
+    }
+
+    fn part1_with(input: &Vec<Pos>, params: &Params) -> Result<i32> {
+        let size = params.get("size")?;
+        part1(input, size, size, params.get("bytes")?)
+    }
+
+    fn part2_with(input: &Vec<Pos>, params: &Params) -> Result<String> {
+        let size = params.get("size")?;
+        part2(input, size, size)
+    }
+
+    fn render(input: &Vec<Pos>, params: &Params, recorder: &mut Recorder) -> Result<()> {
+        render(input, params.get("size")?, recorder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day18.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input, 7, 7, 12).unwrap(), 22);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(part2(&input, 7, 7).unwrap(), "6,1");
+    }
+
+    #[test]
+    fn exit_never_cut_off() {
+        let input = Day18::parse(EXAMPLE).unwrap();
+        assert!(part2(&input, 71, 71).is_err());
+        assert!(part1(&input, 7, 7, input.len()).is_err());
+    }
+}
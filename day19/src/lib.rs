@@ -0,0 +1,179 @@
+use anyhow::Result;
+use aoc_common::{
+    par::*,
+    parse::{self, Line},
+    ParseError, Solution,
+};
+use std::collections::HashMap;
+
+#[derive(Default, Debug)]
+struct TrieNode {
+    children: HashMap<char, TrieNode>,
+    is_word: bool,
+}
+
+#[derive(Default, Debug)]
+struct Trie {
+    root: TrieNode,
+}
+
+impl Trie {
+    fn insert(&mut self, entry: &str) {
+        let mut node = &mut self.root;
+        for ch in entry.chars() {
+            node = node.children.entry(ch).or_default();
+        }
+        node.is_word = true;
+    }
+}
+
+fn is_fillable(design: &str, towels: &Trie, from: usize, dp: &mut Vec<Option<bool>>) -> bool {
+    match dp.get(from) {
+        None => true,
+        Some(&Some(fillable)) => fillable,
+        Some(None) => {
+            let fillable = (|| {
+                let mut node = Some(&towels.root);
+                let mut idx = from;
+                while node.is_some() {
+                    if node.unwrap().is_word && is_fillable(design, towels, idx, dp) {
+                        return true;
+                    }
+                    if idx >= design.len() {
+                        return false;
+                    }
+                    node = node
+                        .unwrap()
+                        .children
+                        .get(&(design.as_bytes()[idx] as char));
+                    idx += 1;
+                }
+                false
+            })();
+            dp[from] = Some(fillable);
+            fillable
+        }
+    }
+}
+
+fn part1(towels: &[String], designs: &[String]) -> usize {
+    let towels = towels.iter().fold(Trie::default(), |mut towels, towel| {
+        towels.insert(towel);
+        towels
+    });
+    designs
+        .par_iter()
+        .filter(|design| {
+            let mut dp: Vec<Option<bool>> = vec![None; design.len()];
+            is_fillable(design, &towels, 0, &mut dp)
+        })
+        .count()
+}
+
+fn num_combinations(
+    design: &str,
+    towels: &Trie,
+    from: usize,
+    dp: &mut Vec<Option<usize>>,
+) -> usize {
+    match dp.get(from) {
+        None => 1,
+        Some(&Some(n)) => n,
+        Some(None) => {
+            let num_combos = (from..design.len())
+                .scan(&towels.root, |node, idx| {
+                    if let Some(next_node) = node.children.get(&(design.as_bytes()[idx] as char)) {
+                        *node = next_node;
+                        Some((node.is_word, idx))
+                    } else {
+                        None
+                    }
+                })
+                .filter_map(|(is_word, idx)| {
+                    if is_word {
+                        Some(num_combinations(design, towels, idx + 1, dp))
+                    } else {
+                        None
+                    }
+                })
+                .sum();
+            dp[from] = Some(num_combos);
+            num_combos
+        }
+    }
+}
+
+fn part2(towels: &[String], designs: &[String]) -> usize {
+    let towels = towels.iter().fold(Trie::default(), |mut towels, towel| {
+        towels.insert(towel);
+        towels
+    });
+    designs
+        .par_iter()
+        .map(|design| {
+            let mut dp: Vec<Option<usize>> = vec![None; design.len()];
+
+            num_combinations(design, &towels, 0, &mut dp)
+        })
+        .sum()
+}
+
+fn stripes(line: &Line, text: &str) -> Result<String, ParseError> {
+    if !text.is_empty() && text.chars().all(|ch| "wubrg".contains(ch)) {
+        Ok(text.to_string())
+    } else {
+        Err(line.error(text, "stripes of w, u, b, r and g"))
+    }
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    type Input = (Vec<String>, Vec<String>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = parse::lines(input);
+        let line = lines.expect("the towels")?;
+        let towels = line
+            .text
+            .split(", ")
+            .map(|towel| stripes(&line, towel))
+            .collect::<Result<_, _>>()?;
+        let blank = lines.expect("a blank line")?;
+        blank.end(blank.text)?;
+        let designs = lines
+            .map(|line| stripes(&line, line.text))
+            .collect::<Result<_, _>>()?;
+        Ok((towels, designs))
+    }
+
+    fn part1((towels, designs): &Self::Input) -> Result<usize> {
+        Ok(part1(towels, designs))
+    }
+
+    fn part2((towels, designs): &Self::Input) -> Result<usize> {
+        Ok(part2(towels, designs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day19.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day19::parse(EXAMPLE).unwrap();
+        assert_eq!(Day19::part1(&input).unwrap(), 6);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day19::parse(EXAMPLE).unwrap();
+        assert_eq!(Day19::part2(&input).unwrap(), 16);
+    }
+}
@@ -0,0 +1,174 @@
+use anyhow::{Context, Result};
+use aoc_common::{parse, Dir, Grid, Param, Params, Pos, Solution};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+fn get(grid: &Grid<char>, p: Pos) -> Option<char> {
+    grid.get(p).copied()
+}
+
+fn part1(grid: &Grid<char>, moves_saved: i32) -> Result<usize> {
+    let start_pos = grid.find(&'S').unwrap();
+    let start_dir = Dir::ORTHOGONAL
+        .into_iter()
+        .find(|&dir| get(grid, start_pos + dir) == Some('.'))
+        .context("no track leads away from the start")?;
+    let path = std::iter::successors(Some((start_pos, start_dir)), |&(pos, dir)| {
+        [dir, dir.turn_left(), dir.turn_right()]
+            .into_iter()
+            .map(|dir| (pos + dir, dir))
+            .find(|&(next_pos, _)| get(grid, next_pos) != Some('#'))
+    })
+    .map(|(pos, _)| pos)
+    .enumerate()
+    .fold(HashMap::new(), |mut path, (idx, pos)| {
+        path.insert(pos, idx);
+        path
+    });
+    let num_cheats = std::iter::successors(Some((start_pos, start_dir)), |&(pos, dir)| {
+        [dir, dir.turn_left(), dir.turn_right()]
+            .into_iter()
+            .map(|dir| (pos + dir, dir))
+            .find(|&(next_pos, _)| get(grid, next_pos) != Some('#'))
+    })
+    .map(|(orig_pos, orig_dir)| {
+        [orig_dir, orig_dir.turn_left(), orig_dir.turn_right()]
+            .into_iter()
+            .filter(|&dir| get(grid, orig_pos + dir) == Some('#'))
+            .map(|dir| orig_pos + dir + dir)
+            .filter(|&cheat_end_pos| get(grid, cheat_end_pos) != Some('#'))
+            .filter(|cheat_end_pos| {
+                path.get(cheat_end_pos).is_some_and(|&cheat_end_idx| {
+                    cheat_end_idx as i32 - path[&orig_pos] as i32 >= moves_saved + 2
+                })
+            })
+            .count()
+    })
+    .sum();
+    Ok(num_cheats)
+}
+
+fn part2(grid: &Grid<char>, moves_saved: usize, cheat_length: usize) -> Result<usize> {
+    let start_pos = grid.find(&'S').unwrap();
+    let start_dir = Dir::ORTHOGONAL
+        .into_iter()
+        .find(|&dir| get(grid, start_pos + dir) == Some('.'))
+        .context("no track leads away from the start")?;
+    let path = std::iter::successors(Some((start_pos, start_dir)), |&(pos, dir)| {
+        [dir, dir.turn_left(), dir.turn_right()]
+            .into_iter()
+            .map(|dir| (pos + dir, dir))
+            .find(|&(next_pos, _)| get(grid, next_pos) != Some('#'))
+    })
+    .map(|(pos, _)| pos)
+    .collect::<Vec<_>>();
+    Ok((0..path.len())
+        .cartesian_product(0..path.len())
+        .filter(|&(i, j)| i < j)
+        .map(|(i, j)| {
+            let p1 = path[i];
+            let p2 = path[j];
+            let old_dist = j - i;
+            let new_dist = p1.manhattan(p2);
+            (path[i], path[j], old_dist, new_dist as usize)
+        })
+        .filter_map(|(p1, p2, old_dist, new_dist)| {
+            if new_dist <= cheat_length && new_dist + moves_saved <= old_dist {
+                Some((p1, p2))
+            } else {
+                None
+            }
+        })
+        .unique()
+        .count())
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    type Input = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "threshold",
+            default: 100,
+            example: 50,
+        },
+        Param {
+            name: "cheat_length",
+            default: 20,
+            example: 20,
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Grid<char>> {
+        let mut lines = parse::lines(input);
+        let grid = Grid::parse_lines(lines.by_ref(), "'.', '#', 'S' or 'E'", |ch| {
+            matches!(ch, '.' | '#' | 'S' | 'E').then_some(ch)
+        })?;
+        for tile in ['S', 'E'] {
+            if grid.find(&tile).is_none() {
+                return Err(lines.end_error(format_args!("a {tile:?} tile")).into());
+            }
+        }
+        Ok(grid)
+    }
+
+    fn part1(grid: &Grid<char>) -> Result<usize> {
+        Self::part1_with(grid, &Self::defaults())
+    }
+
+    fn part2(grid: &Grid<char>) -> Result<usize> {
+        Self::part2_with(grid, &Self::defaults())
+    }
+
+    fn part1_with(grid: &Grid<char>, params: &Params) -> Result<usize> {
+        part1(grid, params.get("threshold")?)
+    }
+
+    fn part2_with(grid: &Grid<char>, params: &Params) -> Result<usize> {
+        let cheat_length = params.get("cheat_length")?;
+        part2(grid, params.get("threshold")?, cheat_length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::ParseError;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day20.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let grid = Day20::parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&grid, 20).unwrap(), 5);
+        assert_eq!(part1(&grid, 64).unwrap(), 1);
+    }
+
+    #[test]
+    fn part2_example() {
+        let grid = Day20::parse(EXAMPLE).unwrap();
+        assert_eq!(part2(&grid, 50, 20).unwrap(), 285);
+        assert_eq!(part2(&grid, 76, 20).unwrap(), 3);
+    }
+
+    #[test]
+    fn start_without_track() {
+        let grid = Day20::parse("#####\n#S#E#\n#####").unwrap();
+        assert!(part1(&grid, 0).is_err());
+        assert!(part2(&grid, 0, 20).is_err());
+    }
+
+    #[test]
+    fn missing_start_is_reported() {
+        let err = Day20::parse("####\n#.E#\n####").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (3, 5, "a 'S' tile")
+        );
+    }
+}
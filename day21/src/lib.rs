@@ -0,0 +1,192 @@
+use anyhow::Result;
+use aoc_common::{parse, Dir, Grid, ParseError, Pos, Solution};
+use itertools::Itertools;
+use std::{collections::HashMap, iter};
+
+const NUMERIC: &str = "789\n456\n123\n.0A";
+const DIRECTIONAL: &str = ".^A\n<v>";
+
+/// A code to type on the door, such as `029A`, and its numeric part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Code {
+    keys: String,
+    value: u64,
+}
+
+/// The position of every key on a keypad, and of the gap no arm may point at.
+struct Keypad {
+    keys: HashMap<char, Pos>,
+    gap: Pos,
+}
+
+impl Keypad {
+    fn new(layout: &str) -> Self {
+        let grid = Grid::parse(layout).unwrap();
+        let keys = grid
+            .iter()
+            .map(|(pos, &key)| (key, pos))
+            .collect::<HashMap<_, _>>();
+        Keypad {
+            gap: keys[&'.'],
+            keys,
+        }
+    }
+
+    /// The arrows that move an arm from `from` to `to` and press it. Only all horizontal moves
+    /// then all vertical ones, or the other way round, can be cheapest: zigzagging makes the
+    /// arm above travel further. A route is left out if its corner is the gap.
+    fn routes(&self, from: char, to: char) -> Vec<Vec<char>> {
+        let (from, to) = (self.keys[&from], self.keys[&to]);
+        let delta = to - from;
+        let horizontal = iter::repeat_n(
+            if delta.c < 0 { Dir::Left } else { Dir::Right },
+            delta.c.unsigned_abs() as usize,
+        );
+        let vertical = iter::repeat_n(
+            if delta.r < 0 { Dir::Up } else { Dir::Down },
+            delta.r.unsigned_abs() as usize,
+        );
+        let mut routes = Vec::new();
+        if Pos::new(from.r, to.c) != self.gap {
+            routes.push(horizontal.clone().chain(vertical.clone()));
+        }
+        if Pos::new(to.r, from.c) != self.gap {
+            routes.push(vertical.chain(horizontal));
+        }
+        routes
+            .into_iter()
+            .map(|route| route.filter_map(Dir::arrow).chain(['A']).collect())
+            .dedup()
+            .collect()
+    }
+}
+
+/// Counts the presses the human makes, memoised by the move a robot's arm makes and the number
+/// of robots between that arm and the human.
+struct Presses {
+    numeric: Keypad,
+    directional: Keypad,
+    memo: HashMap<(char, char, usize), u64>,
+}
+
+impl Presses {
+    fn new() -> Self {
+        Presses {
+            numeric: Keypad::new(NUMERIC),
+            directional: Keypad::new(DIRECTIONAL),
+            memo: HashMap::new(),
+        }
+    }
+
+    /// Presses to type `code` on the door, with `robots` robots at directional keypads between
+    /// the human and the robot at the door.
+    fn code(&mut self, code: &str, robots: usize) -> u64 {
+        let mut presses = 0;
+        for (from, to) in iter::once('A').chain(code.chars()).tuple_windows() {
+            let routes = self.numeric.routes(from, to);
+            presses += routes
+                .iter()
+                .map(|route| self.arrows(route, robots))
+                .min()
+                .unwrap();
+        }
+        presses
+    }
+
+    /// Presses to type `keys` on a directional keypad, with `robots` robots pressing it on
+    /// behalf of the human.
+    fn arrows(&mut self, keys: &[char], robots: usize) -> u64 {
+        if robots == 0 {
+            return keys.len() as u64;
+        }
+        iter::once('A')
+            .chain(keys.iter().copied())
+            .tuple_windows()
+            .map(|(from, to)| self.arrow(from, to, robots))
+            .sum()
+    }
+
+    fn arrow(&mut self, from: char, to: char, robots: usize) -> u64 {
+        if let Some(&presses) = self.memo.get(&(from, to, robots)) {
+            return presses;
+        }
+        let routes = self.directional.routes(from, to);
+        let presses = routes
+            .iter()
+            .map(|route| self.arrows(route, robots - 1))
+            .min()
+            .unwrap();
+        self.memo.insert((from, to, robots), presses);
+        presses
+    }
+}
+
+fn complexity(codes: &[Code], robots: usize) -> u64 {
+    let mut presses = Presses::new();
+    codes
+        .iter()
+        .map(|code| presses.code(&code.keys, robots) * code.value)
+        .sum()
+}
+
+fn part1(codes: &[Code]) -> u64 {
+    complexity(codes, 2)
+}
+
+fn part2(codes: &[Code]) -> u64 {
+    complexity(codes, 25)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    type Input = Vec<Code>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Code>> {
+        Ok(parse::lines(input)
+            .map(|line| {
+                let digits = line
+                    .text
+                    .strip_suffix('A')
+                    .ok_or_else(|| line.end_error("'A'"))?;
+                if let Some(offset) = digits.find(|ch: char| !ch.is_ascii_digit()) {
+                    return Err(line.error(&digits[offset..], "a digit"));
+                }
+                Ok(Code {
+                    keys: line.text.to_string(),
+                    value: line.number(digits)?,
+                })
+            })
+            .collect::<Result<_, ParseError>>()?)
+    }
+
+    fn part1(codes: &Vec<Code>) -> Result<u64> {
+        Ok(part1(codes))
+    }
+
+    fn part2(codes: &Vec<Code>) -> Result<u64> {
+        Ok(part2(codes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day21.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day21::parse(EXAMPLE).unwrap();
+        assert_eq!(Day21::part1(&input).unwrap(), 126384);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day21::parse(EXAMPLE).unwrap();
+        assert_eq!(Day21::part2(&input).unwrap(), 154115708116294);
+    }
+}
@@ -0,0 +1,97 @@
+use anyhow::Result;
+use aoc_common::{par::*, parse, ParseError, Solution};
+use std::{collections::HashMap, iter::successors};
+
+fn prune(n: i64) -> i64 {
+    n % 16777216
+}
+
+fn evolve(n: i64) -> i64 {
+    let n = prune(n ^ (n << 6));
+    let n = prune(n ^ (n >> 5));
+
+    prune(n ^ (n << 11))
+}
+
+fn part1(input: &[i64]) -> i64 {
+    input
+        .iter()
+        .map(|&n| successors(Some(n), |&n| Some(evolve(n))).nth(2000).unwrap())
+        .sum()
+}
+
+fn part2(input: &[i64]) -> i64 {
+    input
+        .par_iter()
+        .map(|&n| {
+            let prices = successors(Some(n), |&n| Some(evolve(n)))
+                .map(|n| n % 10)
+                .take(2000)
+                .collect::<Vec<_>>();
+            prices
+                .windows(5)
+                .filter_map(|window| match window {
+                    [a, b, c, d, e] => Some((a, b, c, d, e)),
+                    _ => None,
+                })
+                .map(|(a, b, c, d, e)| ((b - a, c - b, d - c, e - d), *e))
+                .fold(HashMap::new(), |mut hm, (k, v)| {
+                    hm.entry(k).or_insert(v);
+                    hm
+                })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .fold(HashMap::new(), |mut uhm, hm| {
+            hm.into_iter().for_each(|(k, v)| {
+                *uhm.entry(k).or_default() += v;
+            });
+            uhm
+        })
+        .into_values()
+        .max()
+        .unwrap()
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>> {
+        Ok(parse::lines(input)
+            .map(|line| line.number(line.text))
+            .collect::<Result<_, ParseError>>()?)
+    }
+
+    fn part1(input: &Vec<i64>) -> Result<i64> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Vec<i64>) -> Result<i64> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day22.example2.txt");
+    const PRICES: &str = include_str!("../../inputs/day22.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day22::parse(EXAMPLE).unwrap();
+        assert_eq!(Day22::part1(&input).unwrap(), 37327623);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day22::parse(PRICES).unwrap();
+        assert_eq!(Day22::part2(&input).unwrap(), 23);
+    }
+}
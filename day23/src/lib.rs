@@ -0,0 +1,146 @@
+use anyhow::Result;
+use aoc_common::{parse, ParseError, Solution};
+use itertools::Itertools;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Display,
+    str::FromStr,
+};
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
+pub struct Id(u16);
+
+impl Id {
+    fn to_str(self) -> String {
+        let a = self.0 / 26;
+        let b = self.0 % 26;
+        [(a as u8 + b'a') as char, (b as u8 + b'a') as char]
+            .iter()
+            .collect()
+    }
+}
+
+impl Display for Id {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+impl FromStr for Id {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes() {
+            [a @ b'a'..=b'z', b @ b'a'..=b'z'] => {
+                Ok(Id((*a as u16 - 'a' as u16) * 26 + *b as u16 - 'a' as u16))
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+fn part1(input: &[(Id, Id)]) -> usize {
+    let graph: HashMap<Id, Vec<Id>> = input.iter().fold(HashMap::new(), |mut graph, &(v, u)| {
+        graph.entry(v).or_default().push(u);
+        graph.entry(u).or_default().push(v);
+        graph
+    });
+    graph
+        .keys()
+        .tuple_combinations()
+        .filter(|(a, b, c)| graph[a].contains(b) && graph[b].contains(c) && graph[c].contains(a))
+        .filter(|(a, b, c)| {
+            a.to_str().starts_with('t')
+                || b.to_str().starts_with('t')
+                || c.to_str().starts_with('t')
+        })
+        .count()
+}
+
+fn part2(input: &[(Id, Id)]) -> String {
+    let graph: BTreeMap<Id, BTreeSet<Id>> =
+        input.iter().fold(BTreeMap::new(), |mut graph, &(v, u)| {
+            graph.entry(v).or_default().insert(u);
+            graph.entry(u).or_default().insert(v);
+            graph
+        });
+    let groups = graph
+        .into_iter()
+        .map(|(id, mut neighbors)| {
+            neighbors.insert(id);
+            (id, neighbors)
+        })
+        .collect::<HashMap<_, _>>();
+    groups
+        .iter()
+        .tuple_combinations()
+        .filter_map(|((id1, g1), (id2, g2))| {
+            let intersection = g1 & g2;
+            if intersection.contains(id1) && intersection.contains(id2) {
+                Some(intersection)
+            } else {
+                None
+            }
+        })
+        .fold(
+            HashMap::new(),
+            |mut counted: HashMap<BTreeSet<Id>, usize>, group| {
+                *counted.entry(group).or_default() += 1;
+                counted
+            },
+        )
+        .into_iter()
+        .max_by_key(|(_, v)| *v)
+        .unwrap()
+        .0
+        .into_iter()
+        .collect::<Vec<_>>()
+        .into_iter()
+        .join(",")
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    type Input = Vec<(Id, Id)>;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input)
+            .map(|line| {
+                let (a, b) = line.split_once(line.text, "-")?;
+                let a = line.parse(a, "a computer name")?;
+                let b = line.parse(b, "a computer name")?;
+                Ok((a, b))
+            })
+            .collect::<Result<_, ParseError>>()?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<String> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day23.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day23::parse(EXAMPLE).unwrap();
+        assert_eq!(Day23::part1(&input).unwrap(), 7);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day23::parse(EXAMPLE).unwrap();
+        assert_eq!(Day23::part2(&input).unwrap(), "co,de,ka,ta");
+    }
+}
@@ -0,0 +1,346 @@
+use anyhow::{bail, Context, Result};
+use aoc_common::{parse, ParseError, Solution};
+use itertools::Itertools;
+use std::{collections::HashMap, fmt::Display, iter, str::FromStr};
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Wire([char; 3]);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+impl Wire {
+    /// `prefix` followed by `bit` in two digits, such as `z07`. Panics if `bit` is over 99.
+    fn numbered(prefix: char, bit: u32) -> Self {
+        let digit = |n| char::from_digit(n, 10).unwrap();
+        Wire([prefix, digit(bit / 10), digit(bit % 10)])
+    }
+
+    /// The number of an `x`, `y` or `z` wire named with `prefix`.
+    fn bit(self, prefix: char) -> Option<u32> {
+        if self.0[0] != prefix {
+            return None;
+        }
+        Some(self.0[1].to_digit(10)? * 10 + self.0[2].to_digit(10)?)
+    }
+}
+
+impl Op {
+    fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Op::And => a && b,
+            Op::Or => a || b,
+            Op::Xor => a ^ b,
+        }
+    }
+}
+
+impl FromStr for Wire {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes() {
+            &[a, b, c] if s.bytes().all(|ch| ch.is_ascii_alphanumeric()) => {
+                Ok(Wire([a as char, b as char, c as char]))
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for Wire {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.iter().collect::<String>())
+    }
+}
+
+fn calculate(
+    wire: Wire,
+    connections: &HashMap<Wire, (Wire, Wire, Op)>,
+    calculated: &mut HashMap<Wire, bool>,
+) -> bool {
+    match calculated.get(&wire) {
+        Some(&value) => value,
+        None => {
+            let (w1, w2, op) = *connections.get(&wire).unwrap();
+            let new_value = op.apply(
+                calculate(w1, connections, calculated),
+                calculate(w2, connections, calculated),
+            );
+            calculated.insert(wire, new_value);
+            new_value
+        }
+    }
+}
+
+fn part1(initials: &HashMap<Wire, bool>, connections: &HashMap<Wire, (Wire, Wire, Op)>) -> u64 {
+    let mut calculated = initials.clone();
+    connections
+        .keys()
+        .filter(|k| k.0[0] == 'z')
+        .map(|k| (k, calculate(*k, connections, &mut calculated)))
+        .map(|(k, v)| {
+            (v as u64)
+                << (k.0[1].to_digit(10).unwrap() as u64 * 10 + k.0[2].to_digit(10).unwrap() as u64)
+        })
+        .fold(0, |a, b| a | b)
+}
+
+/// How many pairs of gate outputs are swapped in the real input.
+const SWAPS: usize = 4;
+
+/// The outputs of gates that break the pattern of a ripple-carry adder over `bits` bits. Bit `i`
+/// computes `p = x ^ y`, `g = x & y`, `z = p ^ carry`, `t = p & carry` and the next carry
+/// `g | t`; bit 0 is a half adder and the last carry is the top bit of `z`.
+fn misplaced(connections: &Connections, bits: u32) -> Vec<Wire> {
+    let feeds = |wire: Wire, op: Op| {
+        connections
+            .values()
+            .any(|&(a, b, o)| o == op && (a == wire || b == wire))
+    };
+    let last = if bits == 1 { Op::And } else { Op::Or };
+    connections
+        .iter()
+        .filter(|&(&out, &(a, _, op))| {
+            let wrong_output = match out.bit('z') {
+                Some(bit) if bit == bits => op != last,
+                Some(_) => op != Op::Xor,
+                None => false,
+            };
+            let input_bit = a.bit('x').or(a.bit('y'));
+            let wrong_use = match (op, input_bit) {
+                (Op::Xor, Some(0)) => out != Wire::numbered('z', 0),
+                (Op::Xor, Some(_)) => !feeds(out, Op::Xor),
+                (Op::Xor, None) => out.bit('z').is_none(),
+                (Op::And, Some(0)) => bits > 1 && !feeds(out, Op::Xor),
+                (Op::And, _) => !feeds(out, Op::Or),
+                (Op::Or, _) => out.bit('z') != Some(bits) && !feeds(out, Op::Xor),
+            };
+            wrong_output || wrong_use
+        })
+        .map(|(&out, _)| out)
+        .sorted()
+        .collect()
+}
+
+/// Splits `wires` into pairs whose outputs, once swapped back, make `connections` add.
+fn swaps(connections: &mut Connections, wires: &[Wire], bits: u32) -> Option<Vec<(Wire, Wire)>> {
+    let Some((&first, rest)) = wires.split_first() else {
+        return adds(connections, bits).then(Vec::new);
+    };
+    for (index, &second) in rest.iter().enumerate() {
+        swap(connections, first, second);
+        let mut others = rest.to_vec();
+        others.remove(index);
+        let found = swaps(connections, &others, bits);
+        swap(connections, first, second);
+        if let Some(mut pairs) = found {
+            pairs.push((first, second));
+            return Some(pairs);
+        }
+    }
+    None
+}
+
+fn swap(connections: &mut Connections, a: Wire, b: Wire) {
+    let gate_a = connections[&a];
+    let gate_b = connections.insert(b, gate_a).unwrap();
+    connections.insert(a, gate_b);
+}
+
+/// Whether the circuit adds every pair of `bits`-bit numbers it is tried on: each bit alone and
+/// with a carry into it, carries rippling through every bit, and some pseudo-random pairs.
+fn adds(connections: &Connections, bits: u32) -> bool {
+    let mask = (1 << bits) - 1;
+    let single = (0..bits).flat_map(|bit| [(1 << bit, 0), (0, 1 << bit), (1 << bit, 1 << bit)]);
+    let mut seed = 0x9e3779b97f4a7c15_u64;
+    let random = iter::repeat_with(move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    })
+    .tuples()
+    .map(|(x, y)| (x & mask, y & mask))
+    .take(32);
+    single
+        .chain([(mask, 1), (1, mask), (mask, mask)])
+        .chain(random)
+        .all(|(x, y)| add(connections, x, y, bits) == Some(x + y))
+}
+
+/// Runs the circuit on `x` and `y`, or `None` if a swap has made it loop.
+fn add(connections: &Connections, x: u64, y: u64, bits: u32) -> Option<u64> {
+    let mut values = HashMap::new();
+    (0..=bits).try_fold(0, |z, bit| {
+        let value = evaluate(Wire::numbered('z', bit), connections, (x, y), &mut values)?;
+        Some(z | (value as u64) << bit)
+    })
+}
+
+/// Like `calculate`, but with `x` and `y` given as numbers and `None` for a wire that depends
+/// on itself.
+fn evaluate(
+    wire: Wire,
+    connections: &Connections,
+    (x, y): (u64, u64),
+    values: &mut HashMap<Wire, Option<bool>>,
+) -> Option<bool> {
+    if let Some(bit) = wire.bit('x') {
+        return Some(x >> bit & 1 == 1);
+    }
+    if let Some(bit) = wire.bit('y') {
+        return Some(y >> bit & 1 == 1);
+    }
+    if let Some(&value) = values.get(&wire) {
+        return value;
+    }
+    values.insert(wire, None);
+    let &(a, b, op) = connections.get(&wire)?;
+    let a = evaluate(a, connections, (x, y), values)?;
+    let b = evaluate(b, connections, (x, y), values)?;
+    let value = op.apply(a, b);
+    values.insert(wire, Some(value));
+    Some(value)
+}
+
+fn part2(connections: &Connections) -> Result<String> {
+    let bits = connections
+        .values()
+        .flat_map(|&(a, b, _)| [a, b])
+        .filter_map(|wire| wire.bit('x'))
+        .max()
+        .context("the circuit reads no x wires")?
+        + 1;
+    if bits >= u64::BITS {
+        bail!(
+            "the circuit adds {bits}-bit numbers, but sums are checked in {} bits",
+            u64::BITS
+        );
+    }
+    let wires = misplaced(connections, bits);
+    if wires.len() != 2 * SWAPS {
+        bail!(
+            "expected {} gates out of place in the adder, found {}: {}",
+            2 * SWAPS,
+            wires.len(),
+            wires.iter().join(",")
+        );
+    }
+    let pairs = swaps(&mut connections.clone(), &wires, bits)
+        .context("no way of pairing up the misplaced gates makes the circuit add")?;
+    Ok(pairs
+        .into_iter()
+        .flat_map(|(a, b)| [a, b])
+        .sorted()
+        .join(","))
+}
+
+type Connections = HashMap<Wire, (Wire, Wire, Op)>;
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    type Input = (HashMap<Wire, bool>, Connections);
+    type Answer1 = u64;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = parse::lines(input);
+        let initial_values: HashMap<Wire, bool> = lines
+            .block()
+            .map(|line| {
+                let (wire, value) = line.split_once(line.text, ": ")?;
+                let value = match value {
+                    "0" => false,
+                    "1" => true,
+                    _ => return Err(line.error(value, "0 or 1")),
+                };
+                Ok((line.parse(wire, "a wire")?, value))
+            })
+            .collect::<Result<_, ParseError>>()?;
+        // Each gate input with where it was read, to check once every gate is known.
+        let mut inputs = Vec::new();
+        let connections: Connections = lines
+            .map(|line| {
+                let (deps, wire) = line.split_once(line.text, " -> ")?;
+                let (dep1, rest) = line.split_once(deps, " ")?;
+                let (op, dep2) = line.split_once(rest, " ")?;
+                let op = match op {
+                    "AND" => Op::And,
+                    "OR" => Op::Or,
+                    "XOR" => Op::Xor,
+                    _ => return Err(line.error(op, "AND, OR or XOR")),
+                };
+                let dep1_wire = line.parse(dep1, "a wire")?;
+                let dep2_wire = line.parse(dep2, "a wire")?;
+                inputs.extend([(line, dep1, dep1_wire), (line, dep2, dep2_wire)]);
+                Ok((line.parse(wire, "a wire")?, (dep1_wire, dep2_wire, op)))
+            })
+            .collect::<Result<_, ParseError>>()?;
+        for (line, text, wire) in inputs {
+            if !initial_values.contains_key(&wire) && !connections.contains_key(&wire) {
+                return Err(line
+                    .error(text, "a wire with an initial value or a gate")
+                    .into());
+            }
+        }
+        Ok((initial_values, connections))
+    }
+
+    fn part1((initial_values, connections): &Self::Input) -> Result<u64> {
+        Ok(part1(initial_values, connections))
+    }
+
+    fn part2((_, connections): &Self::Input) -> Result<String> {
+        part2(connections)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL: &str = include_str!("../../inputs/day24.example2.txt");
+    const LARGE: &str = include_str!("../../inputs/day24.example.txt");
+    const ADDER: &str = include_str!("../../inputs/day24.adder.txt");
+
+    #[test]
+    fn part1_small_example() {
+        let input = Day24::parse(SMALL).unwrap();
+        assert_eq!(Day24::part1(&input).unwrap(), 4);
+    }
+
+    #[test]
+    fn part1_large_example() {
+        let input = Day24::parse(LARGE).unwrap();
+        assert_eq!(Day24::part1(&input).unwrap(), 2024);
+    }
+
+    #[test]
+    fn part2_swapped_adder() {
+        let input = Day24::parse(ADDER).unwrap();
+        assert_eq!(
+            Day24::part2(&input).unwrap(),
+            "gkn,gzj,lpt,nks,owu,z03,z07,z10"
+        );
+    }
+
+    #[test]
+    fn undefined_wire_is_reported() {
+        let err = Day24::parse("x00: 1\n\nx00 AND abc -> z00\n").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (3, 9, "abc"));
+    }
+
+    #[test]
+    fn part2_too_wide() {
+        let input = Day24::parse("x63: 1\ny63: 1\n\nx63 AND y63 -> z00\n").unwrap();
+        let err = Day24::part2(&input).unwrap_err();
+        assert!(err.to_string().contains("64-bit"), "{err}");
+    }
+}
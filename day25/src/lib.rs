@@ -0,0 +1,72 @@
+use anyhow::{bail, Result};
+use aoc_common::{parse, Grid, Part, Solution};
+use itertools::Itertools;
+
+fn do_match(lock: &[i32], key: &[i32]) -> bool {
+    lock.iter()
+        .zip(key.iter())
+        .map(|(l, k)| l - k)
+        .all(|n| n <= 5)
+}
+
+fn part1(input: &[Vec<i32>]) -> usize {
+    let locks = input.iter().filter(|arr| arr.iter().all(|&n| n >= 0));
+    let keys = input.iter().filter(|arr| arr.iter().all(|&n| n <= 0));
+    locks
+        .cartesian_product(keys)
+        .filter(|(lock, key)| do_match(lock, key))
+        .count()
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    type Input = Vec<Vec<i32>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    const PARTS: &'static [Part] = &[Part::One];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = parse::lines(input);
+        let mut schematics = Vec::new();
+        loop {
+            let block = lines.block().collect::<Vec<_>>();
+            if block.is_empty() {
+                return Ok(schematics);
+            }
+            let mask = Grid::parse_lines(block, "'#' or '.'", |ch| match ch {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            })?;
+            let sign = if mask[(0, 0)] { 1 } else { -1 };
+            schematics.push(
+                mask.columns()
+                    .map(|column| (column.filter(|&&filled| filled).count() as i32 - 1) * sign)
+                    .collect(),
+            );
+        }
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        Ok(part1(input))
+    }
+
+    fn part2(_: &Self::Input) -> Result<usize> {
+        bail!("day 25 has no second part")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day25.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day25::parse(EXAMPLE).unwrap();
+        assert_eq!(Day25::part1(&input).unwrap(), 3);
+    }
+}